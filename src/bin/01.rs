@@ -1,4 +1,6 @@
-advent_of_code::solution!(1);
+use advent_of_code::{debug, rng::Rng};
+
+advent_of_code::solution!(1, generate = generate);
 
 #[derive(Debug)]
 enum Move {
@@ -55,7 +57,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut zero_count = 0;
     for mov in moves {
         let new_pos = do_move(dial_pos, &mov);
-        // println!("{:?}: {} -> {}", mov, dial_pos, new_pos);
+        debug!(level = 2, "{mov:?}: {dial_pos} -> {new_pos}");
         dial_pos = new_pos;
         if dial_pos == 0 {
             zero_count += 1;
@@ -71,10 +73,10 @@ pub fn part_two(input: &str) -> Option<u64> {
     for mov in moves {
         let zero_crossings = count_move_zero_clicks(dial_pos, &mov);
         let new_pos = do_move(dial_pos, &mov);
-        // println!(
-        //     "{:?}: {} -> {} ({})",
-        //     mov, dial_pos, new_pos, zero_crossings
-        // );
+        debug!(
+            level = 2,
+            "{mov:?}: {dial_pos} -> {new_pos} ({zero_crossings})"
+        );
         dial_pos = new_pos;
         zero_count += zero_crossings;
     }
@@ -101,16 +103,19 @@ mod tests {
         assert_eq!(count_move_zero_clicks(82, &Move::Left(30)), 0);
         assert_eq!(count_move_zero_clicks(52, &Move::Right(48)), 1);
     }
+}
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
-    }
+/// `size` rotations of up to 999 clicks each.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let dir = if rng.chance(0.5) { 'L' } else { 'R' };
+            format!("{dir}{}\n", rng.range(1u32..=999))
+        })
+        .collect()
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
+advent_of_code::examples! {
+    test_part_one: part_one(example) == Some(3);
+    test_part_two: part_two(example) == Some(6);
 }
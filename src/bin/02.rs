@@ -1,21 +1,20 @@
-advent_of_code::solution!(2);
+use advent_of_code::{parse, rng::Rng};
+
+advent_of_code::solution!(2, generate = generate);
 
 fn sum_invalid_ids<F>(input: &str, is_invalid: F) -> Option<u64>
 where
     F: Fn(&usize) -> bool,
 {
+    let ranges = parse::lines(input.trim())
+        .parse(|line| parse::separated(line, ',', parse::range::<usize>))
+        .unwrap();
+
     Some(
-        input
-            .trim()
-            .split(',')
-            .map(|part| part.split_once('-').unwrap())
-            .map(|(left, right)| {
-                (
-                    left.parse::<usize>().unwrap(),
-                    right.parse::<usize>().unwrap(),
-                )
-            })
-            .flat_map(|(start, end)| (start..=end).filter(&is_invalid))
+        ranges
+            .into_iter()
+            .flatten()
+            .flat_map(|range| range.filter(&is_invalid))
             .sum::<usize>() as u64,
     )
 }
@@ -47,19 +46,22 @@ pub fn part_two(input: &str) -> Option<u64> {
     })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1227775554));
-    }
+/// `size` disjoint ranges of up to 10,000 ids each, in random order.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut start = rng.range(1u64..=1_000);
+    let mut ranges: Vec<String> = (0..size)
+        .map(|_| {
+            let end = start + rng.range(0..=10_000);
+            let range = format!("{start}-{end}");
+            start = end + rng.range(1..=10_000_000);
+            range
+        })
+        .collect();
+    rng.shuffle(&mut ranges);
+    ranges.join(",") + "\n"
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4174379265));
-    }
+advent_of_code::examples! {
+    test_part_one: part_one(example) == Some(1227775554);
+    test_part_two: part_two(example) == Some(4174379265);
 }
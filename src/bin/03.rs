@@ -1,5 +1,6 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, generate = generate);
 
+use advent_of_code::rng::Rng;
 use itertools::Itertools;
 
 fn parse_input(input: &str) -> Vec<Vec<u8>> {
@@ -25,12 +26,9 @@ fn find_max_joltage<const BATTERIES: usize>(bank: &Vec<u8>) -> u64 {
             .chain([digit])
             .tuple_windows()
             .find_position(|(high, low)| low > high);
-        match found {
-            Some((drop_index, _)) => {
-                digits.remove(drop_index);
-                digits.push(*digit);
-            }
-            None => {}
+        if let Some((drop_index, _)) = found {
+            digits.remove(drop_index);
+            digits.push(*digit);
         }
     }
 
@@ -45,19 +43,56 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(parse_input(input).iter().map(find_max_joltage::<12>).sum())
 }
 
+/// `size` banks of 100 batteries.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let bank: String = (0..100)
+                .map(|_| char::from(b'0' + rng.range(1u8..=9)))
+                .collect();
+            bank + "\n"
+        })
+        .collect()
+}
+
+advent_of_code::examples! {
+    test_part_one: part_one(example) == Some(357);
+    test_part_two: part_two(example) == Some(3121910778619);
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use advent_of_code::property::{self, Config};
+    use itertools::Itertools;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(357));
+    use super::find_max_joltage;
+
+    /// Tries every choice of batteries.
+    fn naive_max_joltage<const BATTERIES: usize>(bank: &[u8]) -> u64 {
+        bank.iter()
+            .combinations(BATTERIES)
+            .map(|digits| digits.iter().fold(0, |acc, x| acc * 10 + **x as u64))
+            .max()
+            .unwrap()
+    }
+
+    fn is_bank<const BATTERIES: usize>(bank: &[u8]) -> bool {
+        bank.len() >= BATTERIES && bank.iter().all(|digit| (1..=9).contains(digit))
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3121910778619));
+    fn max_joltage_matches_naive() {
+        property::check_with(
+            &Config::default().valid(|bank: &Vec<u8>| is_bank::<2>(bank)),
+            |rng| property::numbers(rng, 2..=15, 1..=9),
+            |bank| naive_max_joltage::<2>(bank),
+            find_max_joltage::<2>,
+        );
+        property::check_with(
+            &Config::default().valid(|bank: &Vec<u8>| is_bank::<12>(bank)),
+            |rng| property::numbers(rng, 12..=16, 1..=9),
+            |bank| naive_max_joltage::<12>(bank),
+            find_max_joltage::<12>,
+        );
     }
 }
@@ -1,129 +1,95 @@
-use std::{collections::HashSet, hash::RandomState};
-
-advent_of_code::solution!(4);
-
-type BasicCoord = (usize, usize);
-
-fn parse_input(input: &str) -> (HashSet<BasicCoord>, BasicCoord) {
-    let mut rolls = HashSet::new();
-
-    let mut width = 0;
-    let mut height = 0;
-
-    for (y, line) in input.trim().lines().enumerate() {
-        height = y + 1;
-        width = line.len();
-        for (x, char) in line.chars().enumerate() {
-            match char {
-                '@' => {
-                    rolls.insert((x, y));
-                }
-                '.' => {}
-                _ => panic!(),
-            }
-        }
-    }
-    (rolls, (width, height))
-}
-
-fn get_neighbors((x, y): BasicCoord, (width, height): BasicCoord) -> Vec<BasicCoord> {
-    let left_available = x > 0;
-    let right_available = x < width - 1;
-    let top_available = y > 0;
-    let bottom_available = y < height - 1;
-
-    let mut neighbors = Vec::new();
-    if left_available {
-        neighbors.push((x - 1, y));
-    }
-    if right_available {
-        neighbors.push((x + 1, y));
-    }
-    if top_available {
-        neighbors.push((x, y - 1));
-    }
-    if bottom_available {
-        neighbors.push((x, y + 1));
-    }
-
-    if left_available && top_available {
-        neighbors.push((x - 1, y - 1));
-    }
-    if top_available && right_available {
-        neighbors.push((x + 1, y - 1));
-    }
-    if right_available && bottom_available {
-        neighbors.push((x + 1, y + 1));
-    }
-    if bottom_available && left_available {
-        neighbors.push((x - 1, y + 1));
-    }
-
-    neighbors
+use std::collections::HashSet;
+
+use advent_of_code::{
+    debug,
+    grid::{Grid, Pos},
+    property,
+    rng::Rng,
+    trace,
+    viz::{self, Canvas, Cell, Color},
+};
+
+advent_of_code::solution!(4, generate = generate);
+
+fn parse_input(input: &str) -> Grid<bool> {
+    Grid::parse(input.trim(), |char| match char {
+        '@' => true,
+        '.' => false,
+        _ => panic!(),
+    })
 }
 
-fn roll_accessible(coord: BasicCoord, rolls: &HashSet<BasicCoord>, dimensions: BasicCoord) -> bool {
-    get_neighbors(coord, dimensions)
-        .iter()
-        .filter(|neighbor_coord| rolls.contains(neighbor_coord))
+fn roll_accessible(pos: Pos, rolls: &Grid<bool>) -> bool {
+    rolls
+        .neighbors8(pos)
+        .filter(|neighbor_pos| rolls[*neighbor_pos])
         .count()
         < 4
 }
 
-fn find_accessible_rolls(
-    rolls: &HashSet<BasicCoord>,
-    dimensions: BasicCoord,
-) -> impl Iterator<Item = BasicCoord> {
+fn find_accessible_rolls(rolls: &Grid<bool>) -> impl Iterator<Item = Pos> {
     rolls
-        .iter()
-        .filter(move |coord| roll_accessible(**coord, rolls, dimensions))
-        .map(|coord| *coord)
+        .find_all(&true)
+        .filter(move |pos| roll_accessible(*pos, rolls))
 }
 
-fn find_rolls_to_remove(
-    rolls: &HashSet<BasicCoord>,
-    dimensions: BasicCoord,
-    removed_last_round: &Option<Vec<BasicCoord>>,
-) -> Vec<BasicCoord> {
+fn find_rolls_to_remove(rolls: &Grid<bool>, removed_last_round: &Option<Vec<Pos>>) -> Vec<Pos> {
     match removed_last_round {
         Some(last_removed) => {
-            let neighbors_to_check: HashSet<_, RandomState> = HashSet::from_iter(
-                last_removed
-                    .iter()
-                    .flat_map(|coord| get_neighbors(*coord, dimensions))
-                    .filter(|coord| rolls.contains(coord)),
-            );
+            let neighbors_to_check: HashSet<_> = last_removed
+                .iter()
+                .flat_map(|pos| rolls.neighbors8(*pos))
+                .filter(|pos| rolls[*pos])
+                .collect();
 
             neighbors_to_check
-                .iter()
-                .filter(|coord| roll_accessible(**coord, rolls, dimensions))
-                .map(|coord| *coord)
+                .into_iter()
+                .filter(|pos| roll_accessible(*pos, rolls))
                 .collect()
         }
-        None => find_accessible_rolls(rolls, dimensions).collect(),
+        None => find_accessible_rolls(rolls).collect(),
+    }
+}
+
+fn show_removal(rolls: &Grid<bool>, removed: &[Pos]) {
+    let mut canvas = Canvas::from_grid(rolls, |roll| {
+        if *roll {
+            Cell::colored('@', Color::Gray)
+        } else {
+            Cell::EMPTY
+        }
+    });
+    for pos in removed {
+        canvas.set(*pos, Cell::colored('x', Color::Red));
     }
+    viz::frame(&canvas);
+    trace::frame(&canvas);
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let (rolls, dimensions) = parse_input(input);
+    let rolls = parse_input(input);
 
-    Some(find_accessible_rolls(&rolls, dimensions).count() as u64)
+    Some(find_accessible_rolls(&rolls).count() as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (mut rolls, dimensions) = parse_input(input);
+    let mut rolls = parse_input(input);
 
     let mut total_removed = 0;
     let mut removed_last_round: Option<Vec<_>> = None;
 
     loop {
-        let removed_this_round = find_rolls_to_remove(&rolls, dimensions, &removed_last_round);
-        for coord in &removed_this_round {
-            rolls.remove(coord);
+        let removed_this_round = find_rolls_to_remove(&rolls, &removed_last_round);
+        if viz::enabled() || trace::enabled() {
+            show_removal(&rolls, &removed_this_round);
+        }
+        for pos in &removed_this_round {
+            rolls[*pos] = false;
         }
 
         let removed_count = removed_this_round.len();
         total_removed += removed_count;
+        debug!("removed {removed_count} rolls, {total_removed} in total");
 
         removed_last_round = Some(removed_this_round);
 
@@ -135,19 +101,15 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(total_removed as u64)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
-    }
+/// A `size` by `size` floor, about 60% covered in rolls.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let floor = property::grid(rng, size..=size, size..=size, |rng| {
+        if rng.chance(0.6) { '@' } else { '.' }
+    });
+    format!("{floor}\n")
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(43));
-    }
+advent_of_code::examples! {
+    test_part_one: part_one(example) == Some(13);
+    test_part_two: part_two(example) == Some(43);
 }
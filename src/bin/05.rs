@@ -1,123 +1,116 @@
 use std::ops::RangeInclusive;
 
-use itertools::Itertools;
-
-advent_of_code::solution!(5);
-
-fn parse_input(
-    input: &str,
-) -> (
-    impl Iterator<Item = RangeInclusive<u64>>,
-    impl Iterator<Item = u64>,
-) {
-    let (range_part, ingredients_part) = input.trim().split_once("\n\n").unwrap();
-    let ranges = range_part.lines().map(|line| {
-        let (start, end) = line.split_once('-').unwrap();
-        let start = start.parse().unwrap();
-        let end = end.parse().unwrap();
-        start..=end
-    });
-    let ingredients = ingredients_part.lines().map(|line| line.parse().unwrap());
+use advent_of_code::{interval_set::IntervalSet, parse, property, rng::Rng};
+
+advent_of_code::solution!(5, generate = generate);
+
+fn parse_input(input: &str) -> (Vec<RangeInclusive<u64>>, Vec<u64>) {
+    let mut blocks = parse::blocks(input);
+    let ranges = blocks.next().unwrap().parse(parse::range).unwrap();
+    let ingredients = blocks.next().unwrap().parse(parse::number).unwrap();
     (ranges, ingredients)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
     let (fresh_ranges, ingredients) = parse_input(input);
 
-    let fresh_ranges = fresh_ranges.collect_vec();
+    let fresh_ranges: IntervalSet<u64> = fresh_ranges.into_iter().collect();
 
     Some(
         ingredients
-            .filter(|ingredient| fresh_ranges.iter().any(|range| range.contains(ingredient)))
+            .into_iter()
+            .filter(|ingredient| fresh_ranges.contains(*ingredient))
             .count() as u64,
     )
 }
 
-fn find_range_conflicts(
-    range: &RangeInclusive<u64>,
-    ranges: &Vec<RangeInclusive<u64>>,
-) -> Vec<usize> {
-    ranges
-        .iter()
-        .enumerate()
-        .filter_map(|(idx, cmp_range)| {
-            let lower_above_min = range.start() >= cmp_range.start();
-            let upper_below_max = range.end() <= cmp_range.end();
-
-            if (lower_above_min && upper_below_max) // range fully covered by cmp_range
-                || (!lower_above_min && !upper_below_max) // cmp_range fully covered by range
-                || (lower_above_min && range.start() <= cmp_range.end()) // range extends upper end of cmp_range
-                || (upper_below_max && range.end() >= cmp_range.start())
-            // range extends lower end of cmp_range
-            {
-                Some(idx)
-            } else {
-                None
-            }
-        })
-        .collect()
-}
-
 pub fn part_two(input: &str) -> Option<u64> {
     let (fresh_ranges, _) = parse_input(input);
 
-    let mut built_ranges = vec![];
-
-    for range in fresh_ranges {
-        let range_conflicts = find_range_conflicts(&range, &built_ranges);
-        if range_conflicts.is_empty() {
-            built_ranges.push(range);
-        } else {
-            let all_conflicting_indices = range_conflicts.iter().sorted().rev().collect_vec();
-
-            let start_value = all_conflicting_indices
-                .iter()
-                .map(|idx| *(built_ranges[**idx].start()))
-                .chain([*range.start()])
-                .min()
-                .unwrap();
-            let end_value = all_conflicting_indices
-                .iter()
-                .map(|idx| *(built_ranges[**idx].end()))
-                .chain([*range.end()])
-                .max()
-                .unwrap();
-
-            for (idx, range) in all_conflicting_indices
-                .iter()
-                .map(|idx| (**idx, built_ranges[**idx].clone()))
-                .collect_vec()
-            {
-                assert_eq!(built_ranges.remove(idx), range);
-            }
-
-            let new_range = start_value..=end_value;
-
-            built_ranges.push(new_range);
-        }
-    }
+    fresh_ranges
+        .into_iter()
+        .collect::<IntervalSet<u64>>()
+        .covered_len()
+}
 
-    Some(
-        built_ranges
-            .iter()
-            .map(|range| 1 + range.end() - range.start())
-            .sum(),
-    )
+/// `size` fresh ranges, which may overlap, followed by `size` ingredient ids.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const MAX_ID: u64 = 100_000_000_000_000;
+    let ranges = property::ranges(rng, size..=size, 1..=MAX_ID, MAX_ID / 100);
+    let ingredients = property::numbers(rng, size..=size, 1..=MAX_ID);
+
+    let ranges = ranges
+        .iter()
+        .map(|range| format!("{}-{}\n", range.start(), range.end()));
+    let ingredients = ingredients.iter().map(|id| format!("{id}\n"));
+    ranges
+        .chain(["\n".to_string()])
+        .chain(ingredients)
+        .collect()
+}
+
+advent_of_code::examples! {
+    test_part_one: part_one(example) == Some(3);
+    test_part_two: part_two(example) == Some(14);
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use std::{collections::HashSet, ops::RangeInclusive};
+
+    use advent_of_code::property::{self, Config};
+
+    use super::{part_one, part_two};
+
+    type Database = (Vec<RangeInclusive<u64>>, Vec<u64>);
+
+    fn format_input((ranges, ingredients): &Database) -> String {
+        let ranges = ranges
+            .iter()
+            .map(|range| format!("{}-{}\n", range.start(), range.end()));
+        let ingredients = ingredients.iter().map(|id| format!("{id}\n"));
+        ranges
+            .chain(["\n".to_string()])
+            .chain(ingredients)
+            .collect()
+    }
+
+    fn generate(rng: &mut advent_of_code::rng::Rng) -> Database {
+        (
+            property::ranges(rng, 1..=8, 0..=60, 12),
+            property::numbers(rng, 1..=10, 0..=70),
+        )
+    }
+
+    fn is_database((ranges, ingredients): &Database) -> bool {
+        !ranges.is_empty() && !ingredients.is_empty()
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
+    fn part_one_matches_naive() {
+        property::check_with(
+            &Config::default().valid(is_database),
+            generate,
+            |(ranges, ingredients)| {
+                let fresh = ingredients
+                    .iter()
+                    .filter(|id| ranges.iter().any(|range| range.contains(id)));
+                Some(fresh.count() as u64)
+            },
+            |database| part_one(&format_input(database)),
+        );
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
+    fn part_two_matches_naive() {
+        property::check_with(
+            &Config::default().valid(is_database),
+            generate,
+            |(ranges, _)| {
+                let fresh: HashSet<u64> = ranges.iter().cloned().flatten().collect();
+                Some(fresh.len() as u64)
+            },
+            |database| part_two(&format_input(database)),
+        );
     }
 }
@@ -1,6 +1,7 @@
+use advent_of_code::rng::Rng;
 use itertools::Itertools;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, generate = generate);
 
 #[derive(Debug, Clone, Copy)]
 enum ProblemType {
@@ -23,7 +24,7 @@ type Problem = (ProblemType, Vec<usize>);
 fn parse_problems_using_normal_math(input: &str) -> Vec<Problem> {
     let mut parts_by_line = input
         .lines()
-        .map(|line| line.trim().split_whitespace().collect_vec())
+        .map(|line| line.split_whitespace().collect_vec())
         .collect_vec();
     let problem_type_line_parts = parts_by_line.pop();
 
@@ -124,19 +125,50 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(calc_problems_sum(problems))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// A worksheet of `size` problems with four numbers of up to four digits each.
+/// Numbers within a problem are randomly aligned to the left or right.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const OPERANDS: usize = 4;
+    let mut lines = vec![String::new(); OPERANDS + 1];
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4277556));
-    }
+    for problem in 0..size {
+        if problem > 0 {
+            lines.iter_mut().for_each(|line| line.push(' '));
+        }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3263827));
+        // sorted by length so that every column of digits reads as one number
+        let mut lengths = (0..OPERANDS).map(|_| rng.range(1..=4)).collect_vec();
+        lengths.sort_unstable();
+        if rng.chance(0.5) {
+            lengths.reverse();
+        }
+
+        let numbers = lengths
+            .iter()
+            .map(|digits| {
+                (0..*digits)
+                    .map(|_| char::from(b'0' + rng.range(1u8..=9)))
+                    .collect::<String>()
+            })
+            .collect_vec();
+        let width = numbers.iter().map(String::len).max().unwrap();
+        let left_aligned = rng.chance(0.5);
+
+        for (line, number) in lines.iter_mut().zip(&numbers) {
+            if left_aligned {
+                line.push_str(&format!("{number:<width$}"));
+            } else {
+                line.push_str(&format!("{number:>width$}"));
+            }
+        }
+        let operator = if rng.chance(0.5) { '+' } else { '*' };
+        lines[OPERANDS].push_str(&format!("{operator:<width$}"));
     }
+
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+advent_of_code::examples! {
+    test_part_one: part_one(example) == Some(4277556);
+    test_part_two: part_two(example) == Some(3263827);
 }
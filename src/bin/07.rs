@@ -1,25 +1,62 @@
-advent_of_code::solution!(7);
+use advent_of_code::{
+    grid::Grid,
+    rng::Rng,
+    trace,
+    viz::{self, Canvas, Cell, Color},
+};
+
+advent_of_code::solution!(7, generate = generate);
+
+fn parse_input(input: &str) -> (Grid<char>, usize) {
+    let manifold = Grid::parse(input, |c| c);
+    let (start_pos, _) = manifold.find(&'S').unwrap();
+    (manifold, start_pos)
+}
+
+fn splitters(row: &[char]) -> impl Iterator<Item = usize> {
+    row.iter()
+        .enumerate()
+        .filter_map(|(i, c)| (*c == '^').then_some(i))
+}
+
+fn manifold_canvas(manifold: &Grid<char>) -> Canvas {
+    Canvas::from_grid(manifold, |c| match c {
+        '^' => Cell::colored('^', Color::Yellow),
+        'S' => Cell::colored('S', Color::Green),
+        _ => Cell::colored('.', Color::Gray),
+    })
+}
+
+/// Adds the beams entering a row to the trace of the beams above it.
+fn show_beams(canvas: &mut Canvas, beams: &[bool], row_idx: usize) {
+    for (x, _) in beams.iter().enumerate().filter(|(_, beam)| **beam) {
+        if canvas
+            .get((x, row_idx))
+            .is_some_and(|cell| cell.symbol != '^')
+        {
+            canvas.set((x, row_idx), Cell::colored('|', Color::Cyan));
+        }
+    }
+    viz::frame(canvas);
+    trace::frame(canvas);
+}
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
-    let first_line = lines.next().unwrap();
-    let columns = first_line.len();
-    let start_pos = first_line.find('S').unwrap();
-
-    let rest_lines = lines.map(|line| {
-        line.chars().enumerate().filter_map(|(i, c)| match c {
-            '^' => Some(i),
-            _ => None,
-        })
-    });
+    let (manifold, start_pos) = parse_input(input);
+    let columns = manifold.width();
 
     let mut beams = vec![false; columns];
     beams[start_pos] = true;
 
     let mut split_count = 0;
+    let mut canvas = (viz::enabled() || trace::enabled()).then(|| manifold_canvas(&manifold));
 
-    for line in rest_lines {
-        for splitter_idx in line {
+    for (row_idx, row) in manifold.rows().enumerate().skip(1) {
+        if let Some(canvas) = &mut canvas {
+            show_beams(canvas, &beams, row_idx);
+        }
+
+        for splitter_idx in splitters(row) {
             if !beams[splitter_idx] {
                 continue;
             }
@@ -40,22 +77,13 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
-    let first_line = lines.next().unwrap();
-    let columns = first_line.len();
-    let start_pos = first_line.find('S').unwrap();
-
-    let rest_lines = lines.map(|line| {
-        line.chars().enumerate().filter_map(|(i, c)| match c {
-            '^' => Some(i),
-            _ => None,
-        })
-    });
+    let (manifold, start_pos) = parse_input(input);
+    let columns = manifold.width();
 
     let mut timelines = vec![0; columns];
 
-    for line in rest_lines.rev() {
-        for splitter_idx in line {
+    for row in manifold.rows().skip(1).rev() {
+        for splitter_idx in splitters(row) {
             let left_timelines = if splitter_idx > 0 {
                 let val = timelines[splitter_idx - 1];
                 if val != 0 { val } else { 1 }
@@ -75,19 +103,30 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(timelines[start_pos])
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+/// A manifold `size` columns wide and at most 140 rows tall. Splitters sit on every other row,
+/// never next to each other, within the cone the beam can reach.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(3) | 1;
+    let height = size.clamp(4, 140) & !1;
+    let center = width / 2;
+
+    let mut manifold = Grid::new(width, height, vec!['.'; width * height]);
+    manifold[(center, 0)] = 'S';
+    for row in (2..height).step_by(2) {
+        let reach = row / 2 - 1;
+        for x in 1..width - 1 {
+            let dist = x.abs_diff(center);
+            let first = row == 2;
+            if dist <= reach && dist % 2 == reach % 2 && (first || rng.chance(0.6)) {
+                manifold[(x, row)] = '^';
+            }
+        }
     }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(40));
-    }
+    format!("{manifold}\n")
+}
+
+advent_of_code::examples! {
+    test_part_one: part_one(example) == Some(21);
+    test_part_two: part_two(example) == Some(40);
 }
@@ -1,88 +1,40 @@
-use std::collections::HashSet;
-
+use advent_of_code::{disjoint_set::DisjointSet, geometry::Point3, parse, rng::Rng};
 use itertools::Itertools;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, generate = generate);
 
-type Coord3D = (usize, usize, usize);
+type Coord3D = Point3<usize>;
 
 fn parse_input(input: &str) -> Vec<Coord3D> {
-    input
-        .lines()
-        .map(|line| {
-            let mut coord_parts = line.trim().splitn(3, ',');
-            let x_part = coord_parts.next().unwrap();
-            let y_part = coord_parts.next().unwrap();
-            let z_part = coord_parts.next().unwrap();
-            assert_eq!(coord_parts.next(), None);
-
-            (
-                x_part.parse().unwrap(),
-                y_part.parse().unwrap(),
-                z_part.parse().unwrap(),
-            )
-        })
-        .collect()
+    parse::lines(input)
+        .parse(|line| parse::tuple(line).map(|[x, y, z]| Point3::new(x, y, z)))
+        .unwrap()
 }
 
-fn get_sorted_connections(
-    coords: &Vec<Coord3D>,
-) -> impl Iterator<Item = ((Coord3D, Coord3D), usize)> {
+/// Pairs of indices into `coords`, sorted by the distance between their coordinates.
+fn get_sorted_connections(coords: &[Coord3D]) -> impl Iterator<Item = ((usize, usize), usize)> {
     coords
         .iter()
+        .enumerate()
         .tuple_combinations()
-        .map(|(a, b)| {
-            let (a_x, a_y, a_z) = a;
-            let (b_x, b_y, b_z) = b;
-
-            let dist = ((*a_x as isize - *b_x as isize).pow(2)
-                + (*a_y as isize - *b_y as isize).pow(2)
-                + (*a_z as isize - *b_z as isize).pow(2))
-            .isqrt() as usize;
-
-            ((*a, *b), dist)
-        })
-        .sorted_by(|(_, dist_a), (_, dist_b)| dist_a.cmp(dist_b))
-}
-
-fn connect_to_circuits(
-    (a_coord, b_coord): &(Coord3D, Coord3D),
-    circuits: &mut Vec<HashSet<Coord3D>>,
-) {
-    let existing_a_circuit = circuits.iter().find_position(|set| set.contains(a_coord));
-    let existing_b_circuit = circuits.iter().find_position(|set| set.contains(b_coord));
-
-    let both_coords = [*a_coord, *b_coord];
-
-    match (existing_a_circuit, existing_b_circuit) {
-        (None, None) => circuits.push(HashSet::from(both_coords)),
-        (None, Some((idx, _))) | (Some((idx, _)), None) => circuits[idx].extend(both_coords),
-        (Some((a_idx, _)), Some((b_idx, b_set))) => {
-            if a_idx != b_idx {
-                let b_set = b_set.clone();
-                circuits[a_idx].extend(b_set);
-                circuits[a_idx].extend(both_coords);
-                circuits.swap_remove(b_idx);
-            }
-        }
-    }
+        .map(|((a_idx, a), (b_idx, b))| ((a_idx, b_idx), a.dist_squared(*b)))
+        .sorted_by_key(|(_, dist)| *dist)
 }
 
 fn get_pt1_ans(input: &str, num_connections: usize) -> Option<u64> {
     let coords = parse_input(input);
     let connections = get_sorted_connections(&coords).take(num_connections);
 
-    let mut circuits: Vec<HashSet<Coord3D>> = vec![];
+    let mut circuits = DisjointSet::new(coords.len());
 
-    for (points, _) in connections {
-        connect_to_circuits(&points, &mut circuits);
+    for ((a_idx, b_idx), _) in connections {
+        circuits.union(a_idx, b_idx);
     }
 
     Some(
         circuits
-            .iter()
-            .map(|set| set.len())
-            .sorted()
+            .component_sizes()
+            .sorted_unstable()
             .rev()
             .take(3)
             .product::<usize>() as u64,
@@ -97,34 +49,28 @@ pub fn part_two(input: &str) -> Option<u64> {
     let coords = parse_input(input);
     let connections = get_sorted_connections(&coords);
 
-    let mut circuits: Vec<HashSet<Coord3D>> = vec![];
-    let mut last_connected_coords: Option<(Coord3D, Coord3D)> = None;
+    let mut circuits = DisjointSet::new(coords.len());
 
-    for (points, _) in connections {
-        connect_to_circuits(&points, &mut circuits);
-
-        if circuits.len() == 1 && circuits[0].len() == coords.len() {
-            last_connected_coords = Some(points);
-            break;
+    for ((a_idx, b_idx), _) in connections {
+        if circuits.union(a_idx, b_idx) && circuits.component_count() == 1 {
+            return Some((coords[a_idx].x * coords[b_idx].x) as u64);
         }
     }
 
-    last_connected_coords.map(|((a_x, _, _), (b_x, _, _))| (a_x * b_x) as u64)
+    None
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = get_pt1_ans(&advent_of_code::template::read_file("examples", DAY), 10);
-        assert_eq!(result, Some(40));
-    }
+/// `size` (at least two) junction boxes in a 100,000 wide cube.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(2))
+        .map(|_| {
+            let [x, y, z] = [(); 3].map(|()| rng.range(0u32..=99_999));
+            format!("{x},{y},{z}\n")
+        })
+        .collect()
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(25272));
-    }
+advent_of_code::examples! {
+    test_part_one: get_pt1_ans(example, 10) == Some(40);
+    test_part_two: part_two(example) == Some(25272);
 }
@@ -1,83 +1,120 @@
 use std::ops::Add;
 
+use advent_of_code::{
+    gf2::{self, BitVec},
+    linear_system::LinearSystem,
+    parse,
+    rng::Rng,
+};
 use itertools::Itertools;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, generate = generate);
 
 struct Machine {
-    desired_light_state: u16,
-    buttons: Vec<u16>,
+    desired_light_state: BitVec,
+    /// The lights (and joltage counters) every button affects.
+    buttons: Vec<BitVec>,
     joltage_requirements: Vec<u16>,
 }
 
 impl Machine {
-    fn from_line(line: &str) -> Self {
-        let mut parts = line.trim().split(' ');
+    fn from_line(line: &str) -> Result<Self, String> {
+        let mut parts = line.split_whitespace();
 
-        let lights_part = parts.next();
+        let lights_part = parts.next().ok_or("expected a light diagram")?;
         let (button_parts, joltage_parts) =
             parts.partition::<Vec<_>, _>(|part| part.starts_with('('));
 
-        assert!(button_parts.len() > 0);
-        assert!(joltage_parts.len() == 1);
-        let joltage_part = joltage_parts.first();
+        if button_parts.is_empty() {
+            return Err("expected at least one button".to_string());
+        }
+        let [joltage_part] = joltage_parts[..] else {
+            return Err("expected a single list of joltage requirements".to_string());
+        };
 
-        let lights_str = lights_part.unwrap();
-        let desired_light_state = lights_str[1..lights_str.len() - 1]
+        let desired_light_state = parse::bracketed(lights_part, '[', ']')?
             .chars()
             .map(|char| match char {
-                '#' => 1,
-                '.' => 0,
-                _ => panic!(),
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(format!("expected '#' or '.', found {char:?}")),
             })
-            .enumerate()
-            .fold(0, |acc, (i, x)| acc | (x << i));
+            .collect::<Result<BitVec, String>>()?;
+        let lights = desired_light_state.len();
 
         let buttons = button_parts
             .iter()
             .map(|button_def| {
-                button_def[1..button_def.len() - 1]
-                    .split(',')
-                    .fold(0, |acc, i| acc | 1 << i.parse::<u8>().unwrap())
+                let mut button = BitVec::new(lights);
+                for light in parse::bracketed_list::<usize>(button_def, '(', ')')? {
+                    if light >= lights {
+                        return Err(format!("button affects unknown light {light}"));
+                    }
+                    button.set(light, true);
+                }
+                Ok(button)
             })
-            .collect();
+            .collect::<Result<_, String>>()?;
 
-        let joltage_str = joltage_part.unwrap();
-        let joltage_requirements = joltage_str[1..joltage_str.len() - 1]
-            .split(',')
-            .map(|num| num.parse().unwrap())
-            .collect();
+        let joltage_requirements: Vec<u16> = parse::bracketed_list(joltage_part, '{', '}')?;
+        if joltage_requirements.len() != lights {
+            return Err("expected one joltage requirement per light".to_string());
+        }
 
-        Self {
+        Ok(Self {
             desired_light_state,
             buttons,
             joltage_requirements,
-        }
+        })
     }
 
-    fn find_min_presses_to_configure(self: &Self) -> Option<u64> {
-        (0..self.buttons.len())
-            .powerset()
-            .find(|buttons_to_try| self.buttons_satisfy_lights(buttons_to_try))
-            .map(|satisfying_buttons| satisfying_buttons.len() as u64)
+    fn find_min_presses_to_configure(&self) -> Option<u64> {
+        // pressing a button twice cancels out, so each button is pressed at most once
+        gf2::solve(&self.buttons, &self.desired_light_state)
+            .map(|solutions| solutions.min_weight().count_ones() as u64)
     }
 
-    fn buttons_satisfy_lights(self: &Self, buttons_to_press: &Vec<usize>) -> bool {
-        let mut lights_val = 0;
+    fn find_min_presses_for_joltage(&self) -> Option<u64> {
+        let counters = self.joltage_requirements.len();
 
-        for button_idx in buttons_to_press {
-            match self.buttons.get(*button_idx) {
-                Some(button_affects) => lights_val ^= button_affects,
-                None => return false,
-            }
-        }
+        // one equation per counter: the presses of all buttons affecting it add up to its requirement
+        let coefficients = (0..counters)
+            .map(|counter| {
+                self.buttons
+                    .iter()
+                    .map(|button| i64::from(button.get(counter)))
+                    .collect()
+            })
+            .collect();
+        let constants = self
+            .joltage_requirements
+            .iter()
+            .map(|x| i64::from(*x))
+            .collect();
+
+        // pressing a button more often than any of its counters requires overshoots that counter
+        let upper_bounds: Vec<i64> = self
+            .buttons
+            .iter()
+            .map(|button| {
+                (0..counters)
+                    .filter(|counter| button.get(*counter))
+                    .map(|counter| i64::from(self.joltage_requirements[counter]))
+                    .min()
+                    .unwrap_or(0)
+            })
+            .collect();
 
-        lights_val == self.desired_light_state
+        LinearSystem::new(coefficients, constants)
+            .min_sum_solution(&upper_bounds)
+            .map(|presses| presses.iter().sum::<i64>() as u64)
     }
 }
 
 fn parse_input(input: &str) -> Vec<Machine> {
-    input.trim().lines().map(Machine::from_line).collect()
+    parse::lines(input.trim())
+        .parse(Machine::from_line)
+        .unwrap()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -90,22 +127,115 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    None
+    let machines = parse_input(input);
+
+    machines
+        .iter()
+        .map(Machine::find_min_presses_for_joltage)
+        .fold_options(0, Add::add)
+}
+
+/// `size` machines with 3 to 10 lights and up to three more buttons than lights. Every light is
+/// wired to a button, and the target state and joltages come from random button presses.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let lights = rng.range(3..=10);
+            let mut buttons: Vec<Vec<usize>> = (0..rng.range(lights..=lights + 3))
+                .map(|_| {
+                    let mut wired = (0..lights).collect_vec();
+                    rng.shuffle(&mut wired);
+                    wired.truncate(rng.range(1..=lights.min(5)));
+                    wired
+                })
+                .collect();
+            for light in 0..lights {
+                if !buttons.iter().any(|button| button.contains(&light)) {
+                    let idx = rng.range(0..=buttons.len() - 1);
+                    buttons[idx].push(light);
+                }
+            }
+            buttons.iter_mut().for_each(|button| button.sort_unstable());
+            buttons.sort();
+            buttons.dedup();
+
+            let mut desired = vec![false; lights];
+            let mut joltages = vec![0u16; lights];
+            for button in &buttons {
+                let toggled = rng.chance(0.5);
+                let presses = rng.range(0..=20);
+                for light in button {
+                    desired[*light] ^= toggled;
+                    joltages[*light] += presses;
+                }
+            }
+
+            let desired: String = desired
+                .iter()
+                .map(|on| if *on { '#' } else { '.' })
+                .collect();
+            let buttons = buttons
+                .iter()
+                .map(|button| format!("({})", button.iter().join(",")))
+                .join(" ");
+            format!("[{desired}] {buttons} {{{}}}\n", joltages.iter().join(","))
+        })
+        .collect()
+}
+
+advent_of_code::examples! {
+    test_part_one: part_one(example) == Some(7);
+    test_part_two: part_two(example) == Some(33);
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use advent_of_code::{property, rng::Rng};
+    use itertools::Itertools;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
+    use super::{generate, part_one};
+
+    /// Tries every set of buttons, fewest first.
+    fn naive_min_presses(line: &str) -> Option<u64> {
+        let mut parts = line.split_whitespace();
+        let desired: Vec<bool> = parts
+            .next()?
+            .trim_matches(['[', ']'])
+            .chars()
+            .map(|char| char == '#')
+            .collect();
+        let buttons: Vec<Vec<usize>> = parts
+            .filter(|part| part.starts_with('('))
+            .map(|part| {
+                part.trim_matches(['(', ')'])
+                    .split(',')
+                    .map(|light| light.parse().unwrap())
+                    .collect()
+            })
+            .collect();
+
+        buttons
+            .iter()
+            .powerset()
+            .find(|pressed| {
+                let mut lights = vec![false; desired.len()];
+                for light in pressed.iter().copied().flatten() {
+                    lights[*light] ^= true;
+                }
+                lights == desired
+            })
+            .map(|pressed| pressed.len() as u64)
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+    fn part_one_matches_naive() {
+        property::check(
+            |rng: &mut Rng| {
+                let size = rng.range(1..=5);
+                generate(rng, size)
+            },
+            |input| input.lines().map(naive_min_presses).sum(),
+            |input| part_one(input),
+        );
     }
 }
@@ -0,0 +1,95 @@
+/// Debug output for solutions that stays out of benchmarks.
+///
+/// The [`debug!`](crate::debug!) macro prints to stderr if the binary runs with `--verbose` and in
+/// tests, where the output is captured unless a test fails. Release builds compile it to nothing.
+use std::env;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// The verbosity level selected with `--verbose` (1) or `--verbose=N`. 0 disables debug output.
+#[inline]
+pub fn level() -> u8 {
+    LEVEL.load(Ordering::Relaxed)
+}
+
+pub fn set_level(level: u8) {
+    LEVEL.store(level, Ordering::Relaxed);
+}
+
+/// Reads the verbosity level from the arguments. Called by the `solution!` macro.
+pub fn init_from_args() {
+    let level = env::args()
+        .filter_map(|arg| match arg.as_str() {
+            "--verbose" => Some(1),
+            _ => arg.strip_prefix("--verbose=")?.parse().ok(),
+        })
+        .max();
+
+    if let Some(level) = level {
+        if !cfg!(debug_assertions) && level > 0 {
+            eprintln!("Warning: --verbose has no effect in release builds.");
+        }
+        set_level(level);
+    }
+}
+
+/// Silences debug output until the returned guard is dropped.
+#[must_use]
+pub fn pause() -> Paused {
+    Paused {
+        level: LEVEL.swap(0, Ordering::Relaxed),
+    }
+}
+
+/// Restores the verbosity level when dropped, see [`pause`].
+pub struct Paused {
+    level: u8,
+}
+
+impl Drop for Paused {
+    fn drop(&mut self) {
+        set_level(self.level);
+    }
+}
+
+/// Prints a line to stderr, like `eprintln!`, if debug output is enabled.
+///
+/// Messages have a verbosity level of 1 unless another one is given with `level = N`. They are
+/// shown if the binary runs with `--verbose=N` or higher, and always in tests.
+///
+/// ```
+/// # use advent_of_code::debug;
+/// # let (from, to) = (50, 82);
+/// debug!("moved to {to}");
+/// debug!(level = 2, "moved from {from} to {to}");
+/// ```
+#[macro_export]
+macro_rules! debug {
+    (level = $level:expr, $($arg:tt)+) => {
+        if cfg!(debug_assertions) && (cfg!(test) || $crate::debug::level() >= $level) {
+            eprintln!($($arg)+);
+        }
+    };
+    ($($arg:tt)+) => {
+        $crate::debug!(level = 1, $($arg)+)
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{level, pause, set_level};
+
+    #[test]
+    fn pauses_output() {
+        set_level(2);
+        {
+            let _paused = pause();
+            assert_eq!(level(), 0);
+        }
+        assert_eq!(level(), 2);
+        set_level(0);
+    }
+}
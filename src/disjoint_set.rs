@@ -0,0 +1,224 @@
+/// Disjoint sets (union-find) for tracking connected components.
+use std::{collections::HashMap, hash::Hash};
+
+/// Disjoint sets over the elements `0..len`, using path compression and union by size.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// Creates `len` singleton sets.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Adds a new singleton set and returns its element.
+    pub fn push(&mut self) -> usize {
+        let element = self.len();
+        self.parent.push(element);
+        self.size.push(1);
+        self.components += 1;
+        element
+    }
+
+    /// Returns the representative of the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut current = element;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns `false` if they were already the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set containing `element`.
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+
+    /// The number of disjoint sets.
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The sizes of all disjoint sets, in no particular order.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> {
+        self.parent
+            .iter()
+            .enumerate()
+            .filter(|(element, parent)| element == *parent)
+            .map(|(root, _)| self.size[root])
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Disjoint sets over arbitrary hashable values, which are added on first use.
+#[derive(Clone, Debug)]
+pub struct KeyedDisjointSet<K> {
+    indices: HashMap<K, usize>,
+    sets: DisjointSet,
+}
+
+impl<K: Hash + Eq> KeyedDisjointSet<K> {
+    pub fn new() -> Self {
+        Self {
+            indices: HashMap::new(),
+            sets: DisjointSet::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Adds a value as a singleton set, if it is not present yet.
+    pub fn insert(&mut self, value: K) -> usize {
+        *self
+            .indices
+            .entry(value)
+            .or_insert_with(|| self.sets.push())
+    }
+
+    /// Returns the representative of the set containing `value`, if present.
+    pub fn find(&mut self, value: &K) -> Option<usize> {
+        let element = *self.indices.get(value)?;
+        Some(self.sets.find(element))
+    }
+
+    /// Merges the sets containing `a` and `b`, adding them first if necessary.
+    /// Returns `false` if they were already the same set.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.union(a, b)
+    }
+
+    pub fn same_set(&mut self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(a), Some(b)) => self.sets.same_set(*a, *b),
+            _ => false,
+        }
+    }
+
+    /// The number of values in the set containing `value`, or 0 if it is not present.
+    pub fn component_size(&mut self, value: &K) -> usize {
+        match self.indices.get(value) {
+            Some(element) => self.sets.component_size(*element),
+            None => 0,
+        }
+    }
+
+    /// The number of disjoint sets.
+    pub fn component_count(&self) -> usize {
+        self.sets.component_count()
+    }
+
+    /// The sizes of all disjoint sets, in no particular order.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> {
+        self.sets.component_sizes()
+    }
+}
+
+impl<K: Hash + Eq> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DisjointSet, KeyedDisjointSet};
+
+    #[test]
+    fn merges_sets() {
+        let mut sets = DisjointSet::new(5);
+        assert_eq!(sets.component_count(), 5);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 1));
+        assert!(!sets.union(0, 3));
+
+        assert!(sets.same_set(0, 3));
+        assert!(!sets.same_set(0, 4));
+        assert_eq!(sets.component_size(1), 3);
+        assert_eq!(sets.component_count(), 3);
+
+        let mut sizes: Vec<_> = sets.component_sizes().collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 1, 3]);
+    }
+
+    #[test]
+    fn compresses_paths() {
+        let mut sets = DisjointSet::new(4);
+        sets.union(0, 1);
+        sets.union(2, 3);
+        sets.union(0, 2);
+        let root = sets.find(3);
+        assert!((0..4).all(|element| sets.parent[element] == root || element == root));
+    }
+
+    #[test]
+    fn merges_keyed_sets() {
+        let mut sets = KeyedDisjointSet::new();
+        sets.insert("d");
+        assert!(sets.union("a", "b"));
+        assert!(sets.union("b", "c"));
+        assert!(!sets.union("c", "a"));
+
+        assert_eq!(sets.len(), 4);
+        assert_eq!(sets.component_count(), 2);
+        assert_eq!(sets.component_size(&"a"), 3);
+        assert_eq!(sets.component_size(&"e"), 0);
+        assert!(sets.same_set(&"a", &"c"));
+        assert!(!sets.same_set(&"a", &"d"));
+        assert_eq!(sets.find(&"e"), None);
+    }
+}
@@ -0,0 +1,657 @@
+/// Points, directions and axis-aligned segments on integer coordinates.
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, RangeInclusive, Sub, SubAssign},
+};
+
+use itertools::Itertools;
+
+use crate::grid::Grid;
+
+/// An integer type that can be used as a coordinate.
+pub trait Scalar:
+    Copy + Debug + Default + Ord + Hash + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The absolute difference, which always fits for coordinates of the same sign.
+    fn abs_diff(self, other: Self) -> Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    /// Converts to `i128`, wrapping for `u128` values above `i128::MAX`.
+    fn as_i128(self) -> i128;
+    /// Converts from `i128`, wrapping values that do not fit.
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($( $t:ty ),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                #[allow(clippy::cast_possible_wrap)]
+                fn abs_diff(self, other: Self) -> Self {
+                    <$t>::abs_diff(self, other) as Self
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                #[allow(clippy::cast_possible_wrap, clippy::cast_lossless)]
+                fn as_i128(self) -> i128 {
+                    self as i128
+                }
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                fn from_i128(value: i128) -> Self {
+                    value as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four cardinal directions, with y growing towards the south (like rows of a map).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::North | Direction::South)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A point in 2D space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Scalar> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The squared euclidean distance, which orders points like the euclidean distance without rounding.
+    pub fn dist_squared(self, other: Self) -> T {
+        let (dx, dy) = (self.x.abs_diff(other.x), self.y.abs_diff(other.y));
+        dx * dx + dy * dy
+    }
+
+    /// The neighbouring point in a direction, or `None` if it is not representable by `T`.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        self.step_by(direction, T::ONE)
+    }
+
+    /// The point `distance` steps away in a direction, or `None` if it is not representable by `T`.
+    pub fn step_by(self, direction: Direction, distance: T) -> Option<Self> {
+        let Self { x, y } = self;
+        Some(match direction {
+            Direction::North => Self::new(x, y.checked_sub(distance)?),
+            Direction::East => Self::new(x.checked_add(distance)?, y),
+            Direction::South => Self::new(x, y.checked_add(distance)?),
+            Direction::West => Self::new(x.checked_sub(distance)?, y),
+        })
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+/// A point in 3D space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Scalar> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// The squared euclidean distance, which orders points like the euclidean distance without rounding.
+    pub fn dist_squared(self, other: Self) -> T {
+        let (dx, dy, dz) = (
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        );
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+/// Implements component-wise addition and subtraction, scalar multiplication and negation.
+macro_rules! impl_point_ops {
+    ($point:ident { $( $field:ident ),* }) => {
+        impl<T: Scalar> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $( $field: self.$field + other.$field ),* }
+            }
+        }
+
+        impl<T: Scalar> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $( $field: self.$field - other.$field ),* }
+            }
+        }
+
+        impl<T: Scalar> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Scalar> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Scalar> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                Self { $( $field: self.$field * factor ),* }
+            }
+        }
+
+        impl<T: Scalar + Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $( $field: -self.$field ),* }
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point2 { x, y });
+impl_point_ops!(Point3 { x, y, z });
+
+/* -------------------------------------------------------------------------- */
+
+/// A horizontal or vertical line segment between two points, including both ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Segment<T> {
+    pub start: Point2<T>,
+    pub end: Point2<T>,
+}
+
+impl<T: Scalar> Segment<T> {
+    /// # Panics
+    /// If the points are not on the same row or column.
+    pub fn new(start: Point2<T>, end: Point2<T>) -> Self {
+        assert!(
+            start.x == end.x || start.y == end.y,
+            "segment from {start:?} to {end:?} is not axis-aligned"
+        );
+        Self { start, end }
+    }
+
+    /// The direction from start to end, or `None` for a single point.
+    pub fn direction(&self) -> Option<Direction> {
+        let (start, end) = (self.start, self.end);
+        if start == end {
+            None
+        } else if start.x == end.x {
+            Some(if start.y < end.y {
+                Direction::South
+            } else {
+                Direction::North
+            })
+        } else {
+            Some(if start.x < end.x {
+                Direction::East
+            } else {
+                Direction::West
+            })
+        }
+    }
+
+    /// Whether the segment runs along a column. Single points count as both vertical and horizontal.
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    /// Whether the segment runs along a row. Single points count as both vertical and horizontal.
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    /// The distance between the two ends.
+    pub fn len(&self) -> T {
+        self.start.manhattan(self.end)
+    }
+
+    pub fn x_range(&self) -> RangeInclusive<T> {
+        self.start.x.min(self.end.x)..=self.start.x.max(self.end.x)
+    }
+
+    pub fn y_range(&self) -> RangeInclusive<T> {
+        self.start.y.min(self.end.y)..=self.start.y.max(self.end.y)
+    }
+
+    pub fn contains(&self, point: Point2<T>) -> bool {
+        self.x_range().contains(&point.x) && self.y_range().contains(&point.y)
+    }
+
+    /// Whether the segments share at least one point.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let overlap = |a: RangeInclusive<T>, b: RangeInclusive<T>| {
+            a.start() <= b.end() && b.start() <= a.end()
+        };
+        overlap(self.x_range(), other.x_range()) && overlap(self.y_range(), other.y_range())
+    }
+
+    /// Whether a vertical and a horizontal segment cross each other,
+    /// with the crossing point lying strictly between the ends of both segments.
+    pub fn crosses(&self, other: &Self) -> bool {
+        let (vertical, horizontal) = match (self.direction(), other.direction()) {
+            (Some(a), Some(b)) if a.is_vertical() && b.is_horizontal() => (self, other),
+            (Some(a), Some(b)) if a.is_horizontal() && b.is_vertical() => (other, self),
+            _ => return false,
+        };
+        let strictly_within =
+            |range: RangeInclusive<T>, value: T| *range.start() < value && value < *range.end();
+        strictly_within(vertical.y_range(), horizontal.start.y)
+            && strictly_within(horizontal.x_range(), vertical.start.x)
+    }
+
+    /// Iterates over all points of the segment from start to end.
+    pub fn points(&self) -> impl Iterator<Item = Point2<T>> + use<T> {
+        let direction = self.direction();
+        let mut next = Some(self.start);
+        let end = self.end;
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = match direction {
+                Some(direction) if current != end => current.step(direction),
+                _ => None,
+            };
+            Some(current)
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Maps the distinct values of some coordinates to consecutive indices.
+///
+/// Compressed space is divided into cells: one cell per value, one for the values between each
+/// two consecutive values, and one for all values below and above, in ascending order.
+/// Each cell has a fixed position relative to all edges whose coordinates were compressed.
+#[derive(Clone, Debug)]
+pub struct CoordinateCompression<T> {
+    values: Vec<T>,
+}
+
+impl<T: Scalar> CoordinateCompression<T> {
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        Self { values }
+    }
+
+    /// The distinct values in ascending order.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The index of a value among the distinct values.
+    pub fn index(&self, value: T) -> Option<usize> {
+        self.values.binary_search(&value).ok()
+    }
+
+    /// The number of cells in compressed space.
+    pub fn cell_count(&self) -> usize {
+        2 * self.values.len() + 1
+    }
+
+    /// The cell a value falls into.
+    pub fn cell(&self, value: T) -> usize {
+        match self.values.binary_search(&value) {
+            Ok(idx) => 2 * idx + 1,
+            Err(idx) => 2 * idx,
+        }
+    }
+
+    /// Whether any integer falls into a cell. Cells between two adjacent integers are empty.
+    pub fn cell_is_empty(&self, cell: usize) -> bool {
+        let is_inner_gap = cell.is_multiple_of(2) && cell > 0 && cell < 2 * self.values.len();
+        is_inner_gap && self.values[cell / 2] - self.values[cell / 2 - 1] == T::ONE
+    }
+}
+
+/// A polygon whose edges are all horizontal or vertical, with its edges counted as inside.
+///
+/// The inside is computed once on a compressed grid, with 2D prefix sums over the cells that
+/// are outside. This answers whether a rectangle lies inside the polygon with four lookups,
+/// independent of its size.
+#[derive(Clone, Debug)]
+pub struct RectilinearPolygon<T> {
+    xs: CoordinateCompression<T>,
+    ys: CoordinateCompression<T>,
+    inside: Grid<bool>,
+    /// `outside_counts[(x, y)]` is the number of non-empty outside cells left of `x` and above `y`.
+    outside_counts: Grid<usize>,
+}
+
+impl<T: Scalar> RectilinearPolygon<T> {
+    /// Creates a polygon from its vertices in order, where the last vertex connects to the first.
+    ///
+    /// # Panics
+    /// If an edge is not horizontal or vertical.
+    pub fn new(vertices: &[Point2<T>]) -> Self {
+        let xs = CoordinateCompression::new(vertices.iter().map(|v| v.x));
+        let ys = CoordinateCompression::new(vertices.iter().map(|v| v.y));
+        let (width, height) = (xs.cell_count(), ys.cell_count());
+
+        let mut boundary = Grid::filled(width, height, false);
+        for (start, end) in vertices.iter().circular_tuple_windows() {
+            let edge = Segment::new(*start, *end);
+            let x_cells = xs.cell(*edge.x_range().start())..=xs.cell(*edge.x_range().end());
+            for x in x_cells {
+                for y in ys.cell(*edge.y_range().start())..=ys.cell(*edge.y_range().end()) {
+                    boundary[(x, y)] = true;
+                }
+            }
+        }
+
+        // flood fill the outside, starting from the cell below and left of all values
+        let mut inside = Grid::filled(width, height, true);
+        let mut stack = vec![(0, 0)];
+        inside[(0, 0)] = false;
+        while let Some(pos) = stack.pop() {
+            for next in boundary.neighbors4(pos) {
+                if !boundary[next] && inside[next] {
+                    inside[next] = false;
+                    stack.push(next);
+                }
+            }
+        }
+
+        let mut outside_counts = Grid::filled(width + 1, height + 1, 0);
+        for y in 0..height {
+            for x in 0..width {
+                let counts = !inside[(x, y)] && !xs.cell_is_empty(x) && !ys.cell_is_empty(y);
+                outside_counts[(x + 1, y + 1)] =
+                    usize::from(counts) + outside_counts[(x, y + 1)] + outside_counts[(x + 1, y)]
+                        - outside_counts[(x, y)];
+            }
+        }
+
+        Self {
+            xs,
+            ys,
+            inside,
+            outside_counts,
+        }
+    }
+
+    /// Whether a point lies inside the polygon or on its edges.
+    pub fn contains(&self, point: Point2<T>) -> bool {
+        self.inside[(self.xs.cell(point.x), self.ys.cell(point.y))]
+    }
+
+    /// Whether all points of the rectangle spanned by two corners lie inside the polygon or on its edges.
+    pub fn contains_rect(&self, a: Point2<T>, b: Point2<T>) -> bool {
+        let (x1, x2) = (self.xs.cell(a.x.min(b.x)), self.xs.cell(a.x.max(b.x)) + 1);
+        let (y1, y2) = (self.ys.cell(a.y.min(b.y)), self.ys.cell(a.y.max(b.y)) + 1);
+        let counts = &self.outside_counts;
+        counts[(x2, y2)] + counts[(x1, y1)] == counts[(x1, y2)] + counts[(x2, y1)]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CoordinateCompression, Direction, Point2, Point3, RectilinearPolygon, Segment};
+
+    fn segment(start: (i32, i32), end: (i32, i32)) -> Segment<i32> {
+        Segment::new(start.into(), end.into())
+    }
+
+    #[test]
+    fn computes_distances() {
+        let a = Point2::new(1usize, 5);
+        let b = Point2::new(4usize, 1);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.dist_squared(b), 25);
+
+        let c = Point3::new(-1i64, 2, 3);
+        assert_eq!(c.manhattan(Point3::default()), 6);
+        assert_eq!(c.dist_squared(Point3::default()), 14);
+    }
+
+    #[test]
+    fn applies_arithmetic() {
+        let mut a = Point2::new(1, 2);
+        a += Point2::new(3, 4);
+        assert_eq!(a, Point2::new(4, 6));
+        assert_eq!(a - Point2::new(1, 1), Point2::new(3, 5));
+        assert_eq!(a * 2, Point2::new(8, 12));
+        assert_eq!(-Point3::new(1, -2, 3), Point3::new(-1, 2, -3));
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+        }
+    }
+
+    #[test]
+    fn steps_in_directions() {
+        let origin = Point2::new(0usize, 0);
+        assert_eq!(origin.step(Direction::North), None);
+        assert_eq!(origin.step(Direction::South), Some(Point2::new(0, 1)));
+        assert_eq!(origin.step_by(Direction::East, 3), Some(Point2::new(3, 0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_diagonal_segments() {
+        let _ = segment((0, 0), (1, 1));
+    }
+
+    #[test]
+    fn intersects_segments() {
+        let vertical = segment((2, 0), (2, 4));
+        assert_eq!(vertical.direction(), Some(Direction::South));
+        assert!(vertical.intersects(&segment((0, 2), (5, 2))));
+        assert!(vertical.intersects(&segment((2, 4), (5, 4))));
+        assert!(!vertical.intersects(&segment((3, 0), (3, 4))));
+        assert!(vertical.intersects(&segment((2, 3), (2, 8))));
+    }
+
+    #[test]
+    fn crosses_segments() {
+        let vertical = segment((2, 0), (2, 4));
+        assert!(vertical.crosses(&segment((5, 2), (0, 2))));
+        assert!(!vertical.crosses(&segment((2, 2), (5, 2))));
+        assert!(!vertical.crosses(&segment((0, 4), (5, 4))));
+        assert!(!vertical.crosses(&segment((2, 1), (2, 3))));
+    }
+
+    #[test]
+    fn iterates_points() {
+        let points: Vec<_> = segment((3, 1), (1, 1))
+            .points()
+            .map(<(_, _)>::from)
+            .collect();
+        assert_eq!(points, [(3, 1), (2, 1), (1, 1)]);
+        assert_eq!(segment((1, 1), (1, 1)).points().count(), 1);
+    }
+
+    #[test]
+    fn compresses_coordinates() {
+        let xs = CoordinateCompression::new([7, 2, 11, 2, 8]);
+        assert_eq!(xs.values(), [2, 7, 8, 11]);
+        assert_eq!(xs.index(8), Some(2));
+        assert_eq!(xs.index(9), None);
+        assert_eq!(xs.cell_count(), 9);
+        assert_eq!([1, 2, 5, 7, 11, 12].map(|x| xs.cell(x)), [0, 1, 2, 3, 7, 8]);
+        assert!(xs.cell_is_empty(4));
+        assert!(!xs.cell_is_empty(2));
+        assert!(!xs.cell_is_empty(0));
+    }
+
+    fn get_mock_polygon() -> RectilinearPolygon<u32> {
+        // the red tiles of day 09's example
+        let vertices = [
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ];
+        RectilinearPolygon::new(&vertices.map(Point2::from))
+    }
+
+    #[test]
+    fn contains_points() {
+        let polygon = get_mock_polygon();
+        assert!(polygon.contains(Point2::new(7, 1)));
+        assert!(polygon.contains(Point2::new(8, 2)));
+        assert!(polygon.contains(Point2::new(10, 6)));
+        assert!(!polygon.contains(Point2::new(3, 2)));
+        assert!(!polygon.contains(Point2::new(8, 6)));
+        assert!(!polygon.contains(Point2::new(0, 0)));
+    }
+
+    #[test]
+    fn contains_rectangles() {
+        let polygon = get_mock_polygon();
+        assert!(polygon.contains_rect(Point2::new(9, 5), Point2::new(2, 3)));
+        assert!(polygon.contains_rect(Point2::new(7, 1), Point2::new(11, 5)));
+        assert!(!polygon.contains_rect(Point2::new(2, 5), Point2::new(11, 1)));
+        assert!(!polygon.contains_rect(Point2::new(9, 7), Point2::new(7, 3)));
+    }
+
+    #[test]
+    fn ignores_gaps_without_tiles() {
+        // a U shape with a slit between x = 2 and x = 3
+        let vertices = [
+            (0, 0),
+            (2, 0),
+            (2, 3),
+            (3, 3),
+            (3, 0),
+            (5, 0),
+            (5, 5),
+            (0, 5),
+        ];
+        let polygon = RectilinearPolygon::new(&vertices.map(Point2::<i32>::from));
+        assert!(polygon.contains_rect(Point2::new(0, 0), Point2::new(5, 5)));
+    }
+}
@@ -0,0 +1,257 @@
+/// Linear algebra over GF(2), where addition is XOR — e.g. for toggling lights with buttons.
+use std::ops::{BitXor, BitXorAssign};
+
+/// A fixed-length vector of bits.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    /// Creates a vector of `len` zero bits.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// # Panics
+    /// If `idx` is out of bounds.
+    pub fn get(&self, idx: usize) -> bool {
+        assert!(idx < self.len, "bit {idx} is out of bounds");
+        self.words[idx / 64] >> (idx % 64) & 1 == 1
+    }
+
+    /// # Panics
+    /// If `idx` is out of bounds.
+    pub fn set(&mut self, idx: usize, value: bool) {
+        assert!(idx < self.len, "bit {idx} is out of bounds");
+        let mask = 1 << (idx % 64);
+        if value {
+            self.words[idx / 64] |= mask;
+        } else {
+            self.words[idx / 64] &= !mask;
+        }
+    }
+
+    /// The number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Whether no bit is set.
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// Iterates over the indices of all set bits in ascending order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|idx| self.get(*idx))
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let bits: Vec<bool> = iter.into_iter().collect();
+        let mut vec = Self::new(bits.len());
+        for (idx, bit) in bits.into_iter().enumerate() {
+            vec.set(idx, bit);
+        }
+        vec
+    }
+}
+
+impl BitXorAssign<&BitVec> for BitVec {
+    /// # Panics
+    /// If the vectors have different lengths.
+    fn bitxor_assign(&mut self, other: &BitVec) {
+        assert_eq!(self.len, other.len, "bit vectors have different lengths");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a ^= b;
+        }
+    }
+}
+
+impl BitXor<&BitVec> for &BitVec {
+    type Output = BitVec;
+
+    fn bitxor(self, other: &BitVec) -> BitVec {
+        let mut result = self.clone();
+        result ^= other;
+        result
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// All solutions of a linear system over GF(2): `particular` XOR any combination of `null_space`.
+#[derive(Clone, Debug)]
+pub struct Solutions {
+    pub particular: BitVec,
+    /// A basis of the solutions of the homogeneous system.
+    pub null_space: Vec<BitVec>,
+}
+
+impl Solutions {
+    /// Iterates over all solutions. There are `2^null_space.len()` of them.
+    pub fn iter(&self) -> impl Iterator<Item = BitVec> + '_ {
+        // walk the combinations in gray code order, so every step flips a single basis vector
+        let mut current = Some(self.particular.clone());
+        let mut step: u64 = 0;
+        std::iter::from_fn(move || {
+            let solution = current.take()?;
+            step += 1;
+            let flip = step.trailing_zeros() as usize;
+            if flip < self.null_space.len() {
+                current = Some(&solution ^ &self.null_space[flip]);
+            }
+            Some(solution)
+        })
+    }
+
+    /// A solution with the fewest set bits.
+    pub fn min_weight(&self) -> BitVec {
+        self.iter().min_by_key(BitVec::count_ones).unwrap()
+    }
+}
+
+/// Solves `Σ x[j] · columns[j] = target` over GF(2), where all columns have the length of `target`.
+/// Returns `None` if there is no solution.
+///
+/// ```
+/// # use advent_of_code::gf2::{BitVec, solve};
+/// let columns: Vec<BitVec> = ["110", "011", "101"]
+///     .iter()
+///     .map(|s| s.chars().map(|c| c == '1').collect())
+///     .collect();
+/// let target: BitVec = [true, false, true].into_iter().collect();
+/// let solutions = solve(&columns, &target).unwrap();
+/// assert_eq!(solutions.min_weight().count_ones(), 1);
+/// ```
+pub fn solve(columns: &[BitVec], target: &BitVec) -> Option<Solutions> {
+    let variables = columns.len();
+
+    // one row per equation, with the target as an extra last bit
+    let mut rows: Vec<BitVec> = (0..target.len())
+        .map(|eq| {
+            columns
+                .iter()
+                .map(|column| column.get(eq))
+                .chain([target.get(eq)])
+                .collect()
+        })
+        .collect();
+
+    let mut pivot_columns = vec![];
+    for column in 0..variables {
+        let rank = pivot_columns.len();
+        let Some(pivot) = (rank..rows.len()).find(|r| rows[*r].get(column)) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+
+        let pivot_row = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != rank && row.get(column) {
+                *row ^= &pivot_row;
+            }
+        }
+        pivot_columns.push(column);
+    }
+
+    // rows without a pivot read 0 = target bit
+    if rows[pivot_columns.len()..]
+        .iter()
+        .any(|row| row.get(variables))
+    {
+        return None;
+    }
+
+    let mut particular = BitVec::new(variables);
+    for (row, column) in rows.iter().zip(&pivot_columns) {
+        particular.set(*column, row.get(variables));
+    }
+
+    let null_space = (0..variables)
+        .filter(|column| !pivot_columns.contains(column))
+        .map(|free| {
+            let mut basis = BitVec::new(variables);
+            basis.set(free, true);
+            for (row, column) in rows.iter().zip(&pivot_columns) {
+                basis.set(*column, row.get(free));
+            }
+            basis
+        })
+        .collect();
+
+    Some(Solutions {
+        particular,
+        null_space,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BitVec, solve};
+
+    fn bits(s: &str) -> BitVec {
+        s.chars().map(|c| c == '1').collect()
+    }
+
+    #[test]
+    fn stores_bits() {
+        let mut vec = BitVec::new(130);
+        vec.set(0, true);
+        vec.set(129, true);
+        vec.set(64, true);
+        vec.set(64, false);
+        assert!(vec.get(129));
+        assert!(!vec.get(64));
+        assert_eq!(vec.count_ones(), 2);
+        assert_eq!(vec.iter_ones().collect::<Vec<_>>(), [0, 129]);
+
+        vec ^= &vec.clone();
+        assert!(vec.is_zero());
+    }
+
+    #[test]
+    fn finds_min_weight_solutions() {
+        // first machine of day 10's example: [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1)
+        let columns: Vec<_> = ["0001", "0101", "0010", "0011", "1010", "1100"]
+            .map(bits)
+            .to_vec();
+        let target = bits("0110");
+
+        let solutions = solve(&columns, &target).unwrap();
+        assert_eq!(solutions.null_space.len(), 2);
+        assert_eq!(solutions.iter().count(), 4);
+        assert_eq!(solutions.min_weight().count_ones(), 2);
+
+        for solution in solutions.iter() {
+            let mut lights = BitVec::new(4);
+            for button in solution.iter_ones() {
+                lights ^= &columns[button];
+            }
+            assert_eq!(lights, target);
+        }
+    }
+
+    #[test]
+    fn detects_unsolvable_systems() {
+        let columns = vec![bits("11"), bits("11")];
+        assert!(solve(&columns, &bits("10")).is_none());
+        assert!(solve(&columns, &bits("00")).is_some());
+    }
+}
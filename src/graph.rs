@@ -0,0 +1,446 @@
+/// Directed graphs with named nodes, traversals, path counting and shortest paths.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    fmt::Display,
+};
+
+use itertools::Itertools;
+
+/// Index of a node in a [`Graph`]. Ids are assigned in the order nodes are first seen.
+pub type NodeId = usize;
+
+/// Returned when an operation requires a graph without cycles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// A node that lies on a cycle.
+    pub node: String,
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "graph contains a cycle through node \"{}\".", self.node)
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// A directed graph with weighted edges, with node names interned to [`NodeId`]s.
+/// Unweighted edges have a weight of 1.
+///
+/// ```
+/// # use advent_of_code::graph::Graph;
+/// let mut graph = Graph::new();
+/// graph.add_edge("a", "b");
+/// graph.add_edge("a", "c");
+/// graph.add_edge("b", "d");
+/// graph.add_edge("c", "d");
+/// let (a, d) = (graph.id("a").unwrap(), graph.id("d").unwrap());
+/// assert_eq!(graph.count_paths(a, d), Ok(2));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    ids: HashMap<String, NodeId>,
+    names: Vec<String>,
+    outputs: Vec<Vec<(NodeId, u64)>>,
+    inputs: Vec<Vec<(NodeId, u64)>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns the id of a node, adding the node if it does not exist yet.
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+
+        let id = self.names.len();
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        self.outputs.push(vec![]);
+        self.inputs.push(vec![]);
+        id
+    }
+
+    /// Looks up the id of an existing node.
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    /// Iterates over all node ids.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + use<> {
+        0..self.len()
+    }
+
+    /// Adds an edge with weight 1, adding its nodes if necessary.
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        self.add_weighted_edge(from, to, 1);
+    }
+
+    /// Adds a weighted edge, adding its nodes if necessary.
+    pub fn add_weighted_edge(&mut self, from: &str, to: &str, weight: u64) {
+        let from = self.intern(from);
+        let to = self.intern(to);
+        self.outputs[from].push((to, weight));
+        self.inputs[to].push((from, weight));
+    }
+
+    /// Iterates over the nodes an edge leads to from `id`.
+    pub fn successors(&self, id: NodeId) -> impl DoubleEndedIterator<Item = NodeId> + '_ {
+        self.outputs[id].iter().map(|(to, _)| *to)
+    }
+
+    /// Iterates over the nodes an edge leads from to `id`.
+    pub fn predecessors(&self, id: NodeId) -> impl DoubleEndedIterator<Item = NodeId> + '_ {
+        self.inputs[id].iter().map(|(from, _)| *from)
+    }
+
+    /// The outgoing edges of `id` as `(to, weight)`.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, u64)] {
+        &self.outputs[id]
+    }
+
+    /// Breadth-first search, returning the number of edges on a shortest path to every node.
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        distances[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            let next_distance = distances[node].map(|d| d + 1);
+            for next in self.successors(node) {
+                if distances[next].is_none() {
+                    distances[next] = next_distance;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Depth-first search, returning the nodes reachable from `start` in pre-order.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if std::mem::replace(&mut visited[node], true) {
+                continue;
+            }
+            order.push(node);
+            // reversed, so the first successor is visited first
+            stack.extend(self.successors(node).filter(|n| !visited[*n]).rev());
+        }
+
+        order
+    }
+
+    /// Marks every node that can be reached from `start`, including `start` itself.
+    pub fn reachable_from(&self, start: NodeId) -> Vec<bool> {
+        self.mark_reachable(start, &self.outputs)
+    }
+
+    /// Marks every node that can reach `end`, including `end` itself.
+    pub fn reaching(&self, end: NodeId) -> Vec<bool> {
+        self.mark_reachable(end, &self.inputs)
+    }
+
+    fn mark_reachable(&self, start: NodeId, adjacency: &[Vec<(NodeId, u64)>]) -> Vec<bool> {
+        let mut marked = vec![false; self.len()];
+        marked[start] = true;
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            for (next, _) in &adjacency[node] {
+                if !std::mem::replace(&mut marked[*next], true) {
+                    stack.push(*next);
+                }
+            }
+        }
+
+        marked
+    }
+
+    /// Sorts all nodes so that every edge points from an earlier to a later node.
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, CycleError> {
+        self.topological_sort_of(&vec![true; self.len()])
+    }
+
+    /// Topologically sorts the nodes marked in `included`, ignoring all other nodes.
+    fn topological_sort_of(&self, included: &[bool]) -> Result<Vec<NodeId>, CycleError> {
+        let mut in_degrees: Vec<usize> = self
+            .nodes()
+            .map(|node| {
+                self.predecessors(node)
+                    .filter(|pred| included[node] && included[*pred])
+                    .count()
+            })
+            .collect();
+
+        let mut queue: VecDeque<_> = self
+            .nodes()
+            .filter(|node| included[*node] && in_degrees[*node] == 0)
+            .collect();
+        let mut order = vec![];
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.successors(node).filter(|n| included[*n]) {
+                in_degrees[next] -= 1;
+                if in_degrees[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if order.len() < included.iter().filter(|x| **x).count() {
+            return Err(self.find_cycle_node(included, &in_degrees));
+        }
+
+        Ok(order)
+    }
+
+    /// Finds a node on a cycle, given the in-degrees that remain after a topological sort.
+    fn find_cycle_node(&self, included: &[bool], in_degrees: &[usize]) -> CycleError {
+        let remaining = |node: &NodeId| included[*node] && in_degrees[*node] > 0;
+
+        // every remaining node has a remaining predecessor, so walking backwards must repeat a node
+        let mut node = self.nodes().find(remaining).unwrap();
+        let mut seen = vec![false; self.len()];
+        while !std::mem::replace(&mut seen[node], true) {
+            node = self.predecessors(node).find(remaining).unwrap();
+        }
+
+        CycleError {
+            node: self.name(node).to_string(),
+        }
+    }
+
+    /// Counts the distinct paths from `from` to `to`.
+    /// Fails if a cycle lies on any of these paths, as there are infinitely many paths then.
+    pub fn count_paths(&self, from: NodeId, to: NodeId) -> Result<u64, CycleError> {
+        let forward = self.reachable_from(from);
+        let backward = self.reaching(to);
+        let on_path: Vec<bool> = forward.iter().zip(backward).map(|(a, b)| *a && b).collect();
+
+        if !on_path[from] {
+            return Ok(0);
+        }
+
+        let order = self.topological_sort_of(&on_path)?;
+        let mut paths = vec![0; self.len()];
+        paths[to] = 1;
+
+        for node in order.into_iter().rev() {
+            if node != to {
+                paths[node] = self.successors(node).map(|next| paths[next]).sum();
+            }
+        }
+
+        Ok(paths[from])
+    }
+
+    /// Counts the distinct paths from `from` to `to` that pass through all `waypoints`, in any order.
+    pub fn count_paths_via(
+        &self,
+        from: NodeId,
+        waypoints: &[NodeId],
+        to: NodeId,
+    ) -> Result<u64, CycleError> {
+        let mut total = 0;
+
+        for order in waypoints.iter().permutations(waypoints.len()) {
+            let mut product = 1;
+            for (a, b) in std::iter::once(&from)
+                .chain(order)
+                .chain(std::iter::once(&to))
+                .tuple_windows()
+            {
+                product *= self.count_paths(*a, *b)?;
+                if product == 0 {
+                    break;
+                }
+            }
+            total += product;
+        }
+
+        Ok(total)
+    }
+
+    /// Finds a shortest weighted path with Dijkstra's algorithm.
+    /// Returns the total weight and the nodes of the path, including both ends.
+    pub fn dijkstra(&self, from: NodeId, to: NodeId) -> Option<(u64, Vec<NodeId>)> {
+        self.a_star(from, to, |_| 0)
+    }
+
+    /// Finds a shortest weighted path with A*, guided by a `heuristic` that must never
+    /// overestimate the remaining distance to `to`.
+    /// Returns the total weight and the nodes of the path, including both ends.
+    pub fn a_star(
+        &self,
+        from: NodeId,
+        to: NodeId,
+        heuristic: impl Fn(NodeId) -> u64,
+    ) -> Option<(u64, Vec<NodeId>)> {
+        let mut distances = vec![u64::MAX; self.len()];
+        let mut previous = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((heuristic(from), from))]);
+        distances[from] = 0;
+
+        while let Some(Reverse((_, node))) = queue.pop() {
+            if node == to {
+                let mut path = vec![to];
+                while let Some(prev) = previous[*path.last().unwrap()] {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some((distances[to], path));
+            }
+
+            for (next, weight) in self.edges(node) {
+                let distance = distances[node] + weight;
+                if distance < distances[*next] {
+                    distances[*next] = distance;
+                    previous[*next] = Some(node);
+                    queue.push(Reverse((distance + heuristic(*next), *next)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CycleError, Graph};
+
+    fn get_mock_graph() -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in [
+            ("svr", "aaa"),
+            ("svr", "bbb"),
+            ("aaa", "fft"),
+            ("bbb", "fft"),
+            ("fft", "ccc"),
+            ("fft", "dac"),
+            ("ccc", "dac"),
+            ("dac", "out"),
+            ("ccc", "out"),
+        ] {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    #[test]
+    fn interns_names() {
+        let mut graph = get_mock_graph();
+        assert_eq!(graph.len(), 7);
+        assert_eq!(graph.id("svr"), Some(0));
+        assert_eq!(graph.intern("svr"), 0);
+        assert_eq!(graph.name(graph.id("out").unwrap()), "out");
+        assert_eq!(graph.id("you"), None);
+    }
+
+    #[test]
+    fn traverses_graphs() {
+        let graph = get_mock_graph();
+        let id = |name| graph.id(name).unwrap();
+
+        let distances = graph.bfs(id("svr"));
+        assert_eq!(distances[id("out")], Some(4));
+        assert_eq!(graph.bfs(id("fft"))[id("svr")], None);
+
+        let order: Vec<_> = graph
+            .dfs(id("fft"))
+            .iter()
+            .map(|n| graph.name(*n))
+            .collect();
+        assert_eq!(order, ["fft", "ccc", "dac", "out"]);
+
+        assert!(!graph.reachable_from(id("ccc"))[id("fft")]);
+        assert!(graph.reaching(id("ccc"))[id("svr")]);
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let mut graph = get_mock_graph();
+        let order = graph.topological_sort().unwrap();
+        let position = |id| order.iter().position(|n| *n == id).unwrap();
+        for node in graph.nodes() {
+            for next in graph.successors(node) {
+                assert!(position(node) < position(next));
+            }
+        }
+
+        graph.add_edge("dac", "fft");
+        assert!(graph.topological_sort().is_err());
+    }
+
+    #[test]
+    fn counts_paths() {
+        let mut graph = get_mock_graph();
+        let id = |graph: &Graph, name| graph.id(name).unwrap();
+        let (svr, fft, dac, out) = (
+            id(&graph, "svr"),
+            id(&graph, "fft"),
+            id(&graph, "dac"),
+            id(&graph, "out"),
+        );
+
+        assert_eq!(graph.count_paths(svr, out), Ok(6));
+        assert_eq!(graph.count_paths(dac, svr), Ok(0));
+        assert_eq!(graph.count_paths_via(svr, &[dac, fft], out), Ok(4));
+
+        // cycles off the counted paths do not matter
+        graph.add_edge("out", "zzz");
+        graph.add_edge("zzz", "out");
+        assert_eq!(graph.count_paths(svr, dac), Ok(4));
+        assert_eq!(
+            graph.count_paths(svr, out),
+            Err(CycleError {
+                node: "out".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn finds_shortest_paths() {
+        let mut graph = Graph::new();
+        graph.add_weighted_edge("a", "b", 1);
+        graph.add_weighted_edge("b", "d", 5);
+        graph.add_weighted_edge("a", "c", 2);
+        graph.add_weighted_edge("c", "d", 2);
+        graph.add_edge("d", "e");
+        let id = |name| graph.id(name).unwrap();
+
+        let (distance, path) = graph.dijkstra(id("a"), id("e")).unwrap();
+        assert_eq!(distance, 5);
+        assert_eq!(path, [id("a"), id("c"), id("d"), id("e")]);
+
+        let (distance, _) = graph.a_star(id("a"), id("e"), |_| 0).unwrap();
+        assert_eq!(distance, 5);
+        assert_eq!(graph.dijkstra(id("e"), id("a")), None);
+    }
+}
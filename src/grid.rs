@@ -0,0 +1,333 @@
+/// A dense, rectangular 2D grid, e.g. for character maps.
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// A position in a [`Grid`] as `(x, y)`, with `(0, 0)` in the top left corner.
+pub type Pos = (usize, usize);
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// If the number of cells does not match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells, got {}",
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid where every cell has the same value.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a character map, mapping every character to a cell.
+    ///
+    /// ```
+    /// # use advent_of_code::grid::Grid;
+    /// let grid = Grid::parse("..@\n@..\n", |c| c == '@');
+    /// assert_eq!(grid.get((2, 0)), Some(&true));
+    /// ```
+    ///
+    /// # Panics
+    /// If the lines are not all of the same length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let len_before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let line_width = cells.len() - len_before;
+
+            match width {
+                None => width = Some(line_width),
+                Some(width) => assert_eq!(
+                    width,
+                    line_width,
+                    "line {} has {line_width} cells, expected {width}",
+                    y + 1
+                ),
+            }
+            height += 1;
+        }
+
+        Self::new(width.unwrap_or(0), height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether a signed position lies inside the grid.
+    pub fn contains(&self, (x, y): (isize, isize)) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over all positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Iterates over all cells together with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Iterates over the positions of the up to 4 orthogonal neighbours inside the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + use<T> {
+        self.offset_positions(pos, &OFFSETS_4)
+    }
+
+    /// Iterates over the positions of the up to 8 orthogonal and diagonal neighbours inside the grid.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + use<T> {
+        self.offset_positions(pos, &OFFSETS_8)
+    }
+
+    fn offset_positions(
+        &self,
+        (x, y): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + use<T> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let nx = x.checked_add_signed(*dx)?;
+            let ny = y.checked_add_signed(*dy)?;
+            (nx < width && ny < height).then_some((nx, ny))
+        })
+    }
+
+    /// A single row as a slice.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over the rows from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        // NOTE: `chunks` panics for a chunk size of 0, an empty range yields no rows instead.
+        (0..if self.width == 0 { 0 } else { self.height }).map(|y| self.row(y))
+    }
+
+    /// Iterates over the cells of a single column from top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Iterates over the columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Finds the position of the first cell with the given value, row by row.
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, x)| *x == value).map(|(pos, _)| pos)
+    }
+
+    /// Iterates over the positions of all cells with the given value, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, x)| *x == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Creates a grid of the same dimensions by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).rev().cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is out of bounds"))
+    }
+}
+
+/// Displays one line per row, with cells written next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+
+    fn get_mock_grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c)
+    }
+
+    #[test]
+    fn parses_character_maps() {
+        let grid = get_mock_grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], 'e');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_ragged_lines() {
+        let _ = Grid::parse("abc\nde\n", |c| c);
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid = get_mock_grid();
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 0)).count(), 5);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 3);
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.rows().last(), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid = Grid::parse(".#.\n#..\n", |c| c);
+        assert_eq!(grid.find(&'#'), Some((1, 0)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(grid.find_all(&'#').collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+    }
+}
@@ -0,0 +1,270 @@
+/// Sets of integers stored as sorted, disjoint inclusive ranges.
+use std::ops::RangeInclusive;
+
+use crate::geometry::Scalar;
+
+/// A set of integers, stored as sorted ranges that neither overlap nor touch.
+///
+/// ```
+/// # use advent_of_code::interval_set::IntervalSet;
+/// let set: IntervalSet<u64> = [3..=5, 10..=14, 6..=8].into_iter().collect();
+/// assert_eq!(set.iter().collect::<Vec<_>>(), [3..=8, 10..=14]);
+/// assert!(set.contains(7));
+/// assert_eq!(set.covered_len(), Some(11));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+/// `value + 1`, or `None` if that overflows.
+fn successor<T: Scalar>(value: T) -> Option<T> {
+    value.checked_add(T::ONE)
+}
+
+/// `value - 1`, or `None` if that overflows.
+fn predecessor<T: Scalar>(value: T) -> Option<T> {
+    value.checked_sub(T::ONE)
+}
+
+impl<T: Scalar> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of disjoint ranges the set consists of.
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Iterates over the ranges in ascending order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|(start, end)| *start..=*end)
+    }
+
+    /// Adds a range, merging it with all ranges it overlaps or touches. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // first range that ends at or after `start - 1`
+        let first = self
+            .intervals
+            .partition_point(|(_, e)| successor(*e).is_some_and(|after| after < start));
+        // first range that starts after `end + 1`
+        let last = match successor(end) {
+            Some(after) => self.intervals.partition_point(|(s, _)| *s <= after),
+            None => self.intervals.len(),
+        };
+
+        let merged = if first < last {
+            (
+                start.min(self.intervals[first].0),
+                end.max(self.intervals[last - 1].1),
+            )
+        } else {
+            (start, end)
+        };
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Checks whether a value is covered by the set, in logarithmic time.
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|(s, _)| *s <= value);
+        idx > 0 && self.intervals[idx - 1].1 >= value
+    }
+
+    /// The number of values covered by the set, or `None` if it does not fit into `T`,
+    /// e.g. for `0..=u64::MAX`.
+    pub fn covered_len(&self) -> Option<T> {
+        self.intervals
+            .iter()
+            .try_fold(T::ZERO, |acc, (start, end)| {
+                end.checked_sub(*start)?
+                    .checked_add(T::ONE)?
+                    .checked_add(acc)
+            })
+    }
+
+    /// Iterates over the ranges between the ranges of the set, in ascending order.
+    /// Values below the first range or above the last one are not included.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|pair| (pair[0].1 + T::ONE)..=(pair[1].0 - T::ONE))
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.extend(other.iter());
+        result
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a_start, a_end) = self.intervals[i];
+            let (b_start, b_end) = other.intervals[j];
+
+            let start = a_start.max(b_start);
+            let end = a_end.min(b_end);
+            if start <= end {
+                intervals.push((start, end));
+            }
+
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let mut j = 0;
+
+        for (start, end) in self.intervals.iter().copied() {
+            let mut remaining = Some(start);
+
+            while j < other.intervals.len() && other.intervals[j].1 < start {
+                j += 1;
+            }
+
+            for (cut_start, cut_end) in other.intervals[j..].iter().copied() {
+                let Some(current) = remaining else { break };
+                if cut_start > end {
+                    break;
+                }
+                if cut_start > current {
+                    intervals.push((current, predecessor(cut_start).unwrap()));
+                }
+                remaining = successor(cut_end.max(current));
+                if cut_end >= end {
+                    break;
+                }
+            }
+
+            if let Some(current) = remaining
+                && current <= end
+            {
+                intervals.push((current, end));
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+impl<T: Scalar> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Scalar> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use super::IntervalSet;
+
+    fn set(ranges: &[RangeInclusive<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn merges_overlapping_and_adjacent_ranges() {
+        let mut intervals = set(&[10..=14, 16..=20, 3..=5]);
+        assert_eq!(ranges(&intervals), [3..=5, 10..=14, 16..=20]);
+
+        intervals.insert(15..=15);
+        assert_eq!(ranges(&intervals), [3..=5, 10..=20]);
+
+        intervals.insert(0..=12);
+        assert_eq!(ranges(&intervals), [0..=20]);
+
+        #[allow(clippy::reversed_empty_ranges)]
+        intervals.insert(30..=25);
+        assert_eq!(intervals.interval_count(), 1);
+    }
+
+    #[test]
+    fn handles_extreme_values() {
+        let intervals: IntervalSet<u8> = [250..=255, 0..=3, 4..=5].into_iter().collect();
+        assert_eq!(intervals.iter().collect::<Vec<_>>(), [0..=5, 250..=255]);
+        assert!(intervals.contains(255));
+        assert_eq!(intervals.covered_len(), Some(12));
+    }
+
+    #[test]
+    fn counts_values_that_do_not_fit() {
+        let full: IntervalSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(full.covered_len(), None);
+        let full: IntervalSet<i64> = [i64::MIN..=i64::MAX].into_iter().collect();
+        assert_eq!(full.covered_len(), None);
+
+        let halves: IntervalSet<i8> = [-128..=-1, 1..=127].into_iter().collect();
+        assert_eq!(halves.covered_len(), None);
+        let halves: IntervalSet<u8> = [0..=127, 129..=255].into_iter().collect();
+        assert_eq!(halves.covered_len(), Some(255));
+    }
+
+    #[test]
+    fn looks_up_values() {
+        let intervals = set(&[3..=5, 10..=14]);
+        assert!(!intervals.contains(1));
+        assert!(intervals.contains(3));
+        assert!(intervals.contains(5));
+        assert!(!intervals.contains(8));
+        assert!(intervals.contains(14));
+        assert!(!intervals.contains(15));
+        assert_eq!(intervals.covered_len(), Some(8));
+    }
+
+    #[test]
+    fn iterates_gaps() {
+        let intervals = set(&[3..=5, 10..=14, 16..=16]);
+        assert_eq!(intervals.gaps().collect::<Vec<_>>(), [6..=9, 15..=15]);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[0..=10, 20..=30]);
+        let b = set(&[5..=25, 28..=28, 40..=50]);
+
+        assert_eq!(ranges(&a.union(&b)), [0..=30, 40..=50]);
+        assert_eq!(ranges(&a.intersection(&b)), [5..=10, 20..=25, 28..=28]);
+        assert_eq!(ranges(&a.difference(&b)), [0..=4, 26..=27, 29..=30]);
+        assert_eq!(ranges(&b.difference(&a)), [11..=19, 40..=50]);
+        assert!(a.difference(&a).is_empty());
+    }
+}
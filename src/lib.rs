@@ -1,3 +1,16 @@
+pub mod debug;
+pub mod disjoint_set;
+pub mod geometry;
+pub mod gf2;
+pub mod graph;
+pub mod grid;
+pub mod interval_set;
+pub mod linear_system;
+pub mod parse;
+pub mod property;
+pub mod rng;
 pub mod template;
+pub mod trace;
+pub mod viz;
 
 // Use this file to add helper functions and additional modules.
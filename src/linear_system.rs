@@ -0,0 +1,484 @@
+/// Exact solving of small integer linear systems, e.g. "which combination of buttons reaches these counters?".
+///
+/// The system is brought into reduced row echelon form over the rationals, which expresses every
+/// pivot variable in terms of the free variables. The free variables are then searched with
+/// branch and bound, using the variables' upper bounds to prune.
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+    pub const ONE: Self = Self { num: 1, den: 1 };
+
+    /// # Panics
+    /// If the denominator is 0.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "denominator must not be 0");
+        let divisor = gcd(num, den) * den.signum();
+        Self {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+
+    /// The largest integer less than or equal to the value.
+    pub fn floor(self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    /// The smallest integer greater than or equal to the value.
+    pub fn ceil(self) -> i128 {
+        -(-self).floor()
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self {
+            num: i128::from(value),
+            den: 1,
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(
+            self.num * other.den + other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(self.num * other.num, self.den * other.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    /// # Panics
+    /// If `other` is 0.
+    fn div(self, other: Self) -> Self {
+        Self::new(self.num * other.den, self.den * other.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A system of linear equations `A·x = b` over integer variables.
+///
+/// ```
+/// # use advent_of_code::linear_system::LinearSystem;
+/// // x + y = 3, y + z = 5
+/// let system = LinearSystem::new(vec![vec![1, 1, 0], vec![0, 1, 1]], vec![3, 5]);
+/// assert_eq!(system.min_sum_solution(&[5, 5, 5]), Some(vec![0, 3, 2]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LinearSystem {
+    coefficients: Vec<Vec<i64>>,
+    constants: Vec<i64>,
+}
+
+/// A pivot variable expressed in terms of the free variables:
+/// `x[column] = constant - Σ coefficients[k] · x[free_columns[k]]`.
+#[derive(Debug)]
+struct PivotRow {
+    column: usize,
+    constant: Rational,
+    coefficients: Vec<Rational>,
+}
+
+impl LinearSystem {
+    /// Creates the system `coefficients · x = constants`, with one row of coefficients per equation.
+    ///
+    /// # Panics
+    /// If the rows do not all have the same length, or there is not one constant per row.
+    pub fn new(coefficients: Vec<Vec<i64>>, constants: Vec<i64>) -> Self {
+        assert_eq!(coefficients.len(), constants.len());
+        if let Some(first) = coefficients.first() {
+            assert!(coefficients.iter().all(|row| row.len() == first.len()));
+        }
+        Self {
+            coefficients,
+            constants,
+        }
+    }
+
+    /// The number of variables.
+    pub fn variable_count(&self) -> usize {
+        self.coefficients.first().map_or(0, Vec::len)
+    }
+
+    /// Finds a solution with `0 <= x[i] <= upper_bounds[i]` that has the smallest sum of all variables.
+    pub fn min_sum_solution(&self, upper_bounds: &[i64]) -> Option<Vec<i64>> {
+        self.minimize(&vec![1; self.variable_count()], upper_bounds)
+    }
+
+    /// Finds a solution with `0 <= x[i] <= upper_bounds[i]` that minimises `Σ objective[i] · x[i]`.
+    /// Returns `None` if there is no such solution.
+    pub fn minimize(&self, objective: &[i64], upper_bounds: &[i64]) -> Option<Vec<i64>> {
+        let n = self.variable_count();
+        assert_eq!(objective.len(), n);
+        assert_eq!(upper_bounds.len(), n);
+
+        let (pivot_rows, free_columns) = self.eliminate()?;
+        let mut search = Search::new(pivot_rows, free_columns, objective, upper_bounds);
+        search.run(0, Rational::ZERO);
+
+        let free_values = search.best?.1;
+        let mut solution = vec![0; n];
+        for (column, value) in search.free_columns.iter().zip(&free_values) {
+            solution[*column] = *value;
+        }
+        for row in &search.pivot_rows {
+            let value = row.value(&free_values).to_integer().unwrap();
+            solution[row.column] = i64::try_from(value).unwrap();
+        }
+        Some(solution)
+    }
+
+    /// Gaussian elimination to reduced row echelon form.
+    /// Returns `None` if the system has no solution even over the rationals.
+    fn eliminate(&self) -> Option<(Vec<PivotRow>, Vec<usize>)> {
+        let n = self.variable_count();
+        let mut rows: Vec<Vec<Rational>> = self
+            .coefficients
+            .iter()
+            .zip(&self.constants)
+            .map(|(row, constant)| {
+                row.iter()
+                    .chain([constant])
+                    .map(|x| Rational::from(*x))
+                    .collect()
+            })
+            .collect();
+
+        let mut pivot_columns = vec![];
+        for column in 0..n {
+            let rank = pivot_columns.len();
+            let Some(pivot) = (rank..rows.len()).find(|r| !rows[*r][column].is_zero()) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+
+            let factor = rows[rank][column];
+            for x in &mut rows[rank] {
+                *x = *x / factor;
+            }
+
+            let pivot_row = rows[rank].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                let factor = row[column];
+                if r == rank || factor.is_zero() {
+                    continue;
+                }
+                for (x, pivot_x) in row.iter_mut().zip(&pivot_row) {
+                    *x = *x - factor * *pivot_x;
+                }
+            }
+
+            pivot_columns.push(column);
+        }
+
+        // rows without a pivot read 0 = constant
+        if rows[pivot_columns.len()..]
+            .iter()
+            .any(|row| !row[n].is_zero())
+        {
+            return None;
+        }
+
+        let free_columns: Vec<usize> = (0..n).filter(|c| !pivot_columns.contains(c)).collect();
+        let pivot_rows = pivot_columns
+            .iter()
+            .zip(rows)
+            .map(|(column, row)| PivotRow {
+                column: *column,
+                constant: row[n],
+                coefficients: free_columns.iter().map(|f| row[*f]).collect(),
+            })
+            .collect();
+
+        Some((pivot_rows, free_columns))
+    }
+}
+
+impl PivotRow {
+    fn value(&self, free_values: &[i64]) -> Rational {
+        self.coefficients
+            .iter()
+            .zip(free_values)
+            .fold(self.constant, |acc, (c, x)| acc - *c * Rational::from(*x))
+    }
+}
+
+/// Branch and bound over the values of the free variables.
+struct Search {
+    pivot_rows: Vec<PivotRow>,
+    free_columns: Vec<usize>,
+    pivot_bounds: Vec<Rational>,
+    free_bounds: Vec<i64>,
+    /// The objective's constant and its coefficient for every free variable,
+    /// after substituting the pivot variables.
+    objective_constant: Rational,
+    free_objective: Vec<Rational>,
+    /// For every pivot row and free variable index `k`: the smallest and largest value
+    /// the terms of the free variables `k..` can subtract from the pivot variable.
+    term_ranges: Vec<Vec<(Rational, Rational)>>,
+    /// For every free variable index `k`: the smallest value the free variables `k..`
+    /// can add to the objective.
+    objective_lower_bounds: Vec<Rational>,
+    values: Vec<i64>,
+    best: Option<(Rational, Vec<i64>)>,
+}
+
+impl Search {
+    fn new(
+        pivot_rows: Vec<PivotRow>,
+        free_columns: Vec<usize>,
+        objective: &[i64],
+        upper_bounds: &[i64],
+    ) -> Self {
+        let free_bounds: Vec<i64> = free_columns.iter().map(|c| upper_bounds[*c]).collect();
+        let pivot_bounds = pivot_rows
+            .iter()
+            .map(|row| Rational::from(upper_bounds[row.column]))
+            .collect();
+
+        let objective_constant = pivot_rows.iter().fold(Rational::ZERO, |acc, row| {
+            acc + Rational::from(objective[row.column]) * row.constant
+        });
+        let free_objective: Vec<Rational> = free_columns
+            .iter()
+            .enumerate()
+            .map(|(k, column)| {
+                pivot_rows
+                    .iter()
+                    .fold(Rational::from(objective[*column]), |acc, row| {
+                        acc - Rational::from(objective[row.column]) * row.coefficients[k]
+                    })
+            })
+            .collect();
+
+        // the range of `c · x` for `0 <= x <= bound`
+        let range = |c: Rational, bound: i64| {
+            let extreme = c * Rational::from(bound);
+            (extreme.min(Rational::ZERO), extreme.max(Rational::ZERO))
+        };
+
+        let term_ranges = pivot_rows
+            .iter()
+            .map(|row| {
+                let mut suffix = vec![(Rational::ZERO, Rational::ZERO); free_columns.len() + 1];
+                for k in (0..free_columns.len()).rev() {
+                    let (lo, hi) = range(row.coefficients[k], free_bounds[k]);
+                    suffix[k] = (suffix[k + 1].0 + lo, suffix[k + 1].1 + hi);
+                }
+                suffix
+            })
+            .collect();
+
+        let mut objective_lower_bounds = vec![Rational::ZERO; free_columns.len() + 1];
+        for k in (0..free_columns.len()).rev() {
+            objective_lower_bounds[k] =
+                objective_lower_bounds[k + 1] + range(free_objective[k], free_bounds[k]).0;
+        }
+
+        Self {
+            values: vec![0; free_columns.len()],
+            pivot_rows,
+            free_columns,
+            pivot_bounds,
+            free_bounds,
+            objective_constant,
+            free_objective,
+            term_ranges,
+            objective_lower_bounds,
+            best: None,
+        }
+    }
+
+    /// Whether the pivot variables can still end up within their bounds,
+    /// with the free variables before `k` fixed.
+    fn feasible(&self, k: usize) -> bool {
+        self.pivot_rows.iter().enumerate().all(|(p, row)| {
+            let fixed = row.coefficients[..k]
+                .iter()
+                .zip(&self.values)
+                .fold(row.constant, |acc, (c, x)| acc - *c * Rational::from(*x));
+            let (lo, hi) = self.term_ranges[p][k];
+            // the pivot variable lies within [fixed - hi, fixed - lo]
+            fixed - hi <= self.pivot_bounds[p] && fixed - lo >= Rational::ZERO
+        })
+    }
+
+    fn run(&mut self, k: usize, objective_so_far: Rational) {
+        if let Some((best, _)) = self.best
+            && objective_so_far + self.objective_lower_bounds[k] + self.objective_constant >= best
+        {
+            return;
+        }
+        if !self.feasible(k) {
+            return;
+        }
+
+        if k == self.free_columns.len() {
+            let all_integers = self
+                .pivot_rows
+                .iter()
+                .all(|row| row.value(&self.values).to_integer().is_some());
+            if all_integers {
+                let objective = objective_so_far + self.objective_constant;
+                self.best = Some((objective, self.values.clone()));
+            }
+            return;
+        }
+
+        for value in 0..=self.free_bounds[k] {
+            self.values[k] = value;
+            let objective = objective_so_far + self.free_objective[k] * Rational::from(value);
+            self.run(k + 1, objective);
+        }
+        self.values[k] = 0;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{LinearSystem, Rational};
+
+    #[test]
+    fn computes_with_rationals() {
+        let a = Rational::new(6, -4);
+        assert_eq!((a.numerator(), a.denominator()), (-3, 2));
+        assert_eq!(a + Rational::new(1, 2), Rational::from(-1));
+        assert_eq!(a * a, Rational::new(9, 4));
+        assert_eq!(a / Rational::new(3, 1), Rational::new(-1, 2));
+        assert_eq!((a.floor(), a.ceil()), (-2, -1));
+        assert!(a < Rational::ZERO);
+        assert_eq!(a.to_string(), "-3/2");
+        assert_eq!((a * Rational::from(2)).to_integer(), Some(-3));
+    }
+
+    #[test]
+    fn solves_unique_systems() {
+        // 2x + y = 5, x - y = 1
+        let system = LinearSystem::new(vec![vec![2, 1], vec![1, -1]], vec![5, 1]);
+        assert_eq!(system.min_sum_solution(&[10, 10]), Some(vec![2, 1]));
+    }
+
+    #[test]
+    fn searches_free_variables() {
+        // first machine of day 10's example, each button adds 1 to some counters
+        let system = LinearSystem::new(
+            vec![
+                vec![0, 0, 0, 0, 1, 1],
+                vec![0, 1, 0, 0, 0, 1],
+                vec![0, 0, 1, 1, 1, 0],
+                vec![1, 1, 0, 1, 0, 0],
+            ],
+            vec![3, 5, 4, 7],
+        );
+        let solution = system.min_sum_solution(&[7, 5, 4, 4, 3, 3]).unwrap();
+        assert_eq!(solution.iter().sum::<i64>(), 10);
+    }
+
+    #[test]
+    fn respects_integers_and_bounds() {
+        // 2x = 3 has no integer solution
+        let system = LinearSystem::new(vec![vec![2]], vec![3]);
+        assert_eq!(system.min_sum_solution(&[10]), None);
+
+        // x + y = 4 with x <= 1
+        let system = LinearSystem::new(vec![vec![1, 1]], vec![4]);
+        assert_eq!(system.minimize(&[1, 3], &[1, 10]), Some(vec![1, 3]));
+
+        // x = 1, x = 2 is inconsistent
+        let system = LinearSystem::new(vec![vec![1], vec![1]], vec![1, 2]);
+        assert_eq!(system.min_sum_solution(&[10]), None);
+    }
+}
@@ -1,4 +1,8 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+#[cfg(feature = "encrypted-inputs")]
+use advent_of_code::template::commands::encrypt;
+use advent_of_code::template::commands::{
+    all, download, generate, profile, read, replay, scaffold, solve, time, verify,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
@@ -7,8 +11,14 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::{
+        template::{
+            Day,
+            commands::{solve, time},
+        },
+        trace, viz,
+    };
+    use std::{ffi::OsString, path::PathBuf, process};
 
     pub enum AppArguments {
         Download {
@@ -24,39 +34,61 @@ mod args {
         },
         Solve {
             day: Day,
-            release: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            options: solve::Options,
         },
         All {
             release: bool,
         },
         Time {
-            all: bool,
             day: Option<Day>,
-            store: bool,
+            options: time::Options,
+        },
+        Replay {
+            day: Day,
+            path: Option<PathBuf>,
+        },
+        Gen {
+            day: Day,
+            size: Option<usize>,
+            seed: Option<u64>,
+        },
+        ProfileSummary {
+            day: Option<Day>,
+            top: Option<usize>,
+        },
+        Verify,
+        #[cfg(feature = "encrypted-inputs")]
+        Encrypt {
+            day: Option<Day>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        parse_from(pico_args::Arguments::from_env())
+    }
 
+    fn parse_from(
+        mut args: pico_args::Arguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
             },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
-
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
+            Some("time") => AppArguments::Time {
+                options: time::Options {
+                    run_all: args.contains("--all"),
+                    store: args.contains("--store"),
+                    scale: args.contains("--scale"),
+                    seed: args.opt_value_from_str("--seed")?,
+                    track_memory: args.contains("--memory"),
+                    count_events: args.contains("--counters"),
+                    pin: args.contains("--pin"),
+                    core: args.opt_value_from_str("--core")?,
+                },
+                day: args.opt_free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -68,11 +100,67 @@ mod args {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
+            Some("solve") => {
+                // `--trace` records to the default path, `--trace <path>` anywhere else
+                let mut rest = args.finish();
+                let trace = take_optional_value(&mut rest, "--trace", |value| {
+                    value.parse::<Day>().is_err()
+                });
+                args = pico_args::Arguments::from_vec(rest);
+
+                let visualize = args.contains("--visualize");
+                let frames: Option<viz::Output> = args.opt_value_from_str("--frames")?;
+                // `--verbose` selects level 1, `--verbose=N` any other level
+                let verbose = if args.contains("--verbose") {
+                    1
+                } else {
+                    args.opt_value_from_str("--verbose")?.unwrap_or(0)
+                };
+                let day = args.free_from_str()?;
+
+                AppArguments::Solve {
+                    day,
+                    options: solve::Options {
+                        release: args.contains("--release"),
+                        submit_part: args.opt_value_from_str("--submit")?,
+                        dhat: args.contains("--dhat"),
+                        profile: args.contains("--profile"),
+                        all_inputs: args.contains("--all-inputs"),
+                        record: args.contains("--record"),
+                        visualize: frames.or(visualize.then_some(viz::Output::Terminal)),
+                        verbose,
+                        trace: trace.map(|path| {
+                            path.map_or_else(|| trace::default_path(day), PathBuf::from)
+                        }),
+                    },
+                }
+            }
+            Some("replay") => AppArguments::Replay {
+                path: args.opt_value_from_str("--trace")?,
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            },
+            Some("gen") => AppArguments::Gen {
+                size: args.opt_value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?,
+                day: args.free_from_str()?,
+            },
+            Some("profile") => {
+                let top = args.opt_value_from_str("--top")?;
+                match args.subcommand()?.as_deref() {
+                    Some("summary") => AppArguments::ProfileSummary {
+                        day: args.opt_free_from_str()?,
+                        top,
+                    },
+                    _ => {
+                        eprintln!("Usage: cargo profile summary [<day>] [--top <n>]");
+                        process::exit(1);
+                    }
+                }
+            }
+            Some("verify") => AppArguments::Verify,
+            #[cfg(feature = "encrypted-inputs")]
+            Some("encrypt") => AppArguments::Encrypt {
+                day: args.opt_free_from_str()?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,6 +181,67 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Takes a flag that may be followed by a value: `key`, `key=<value>` or `key <value>`.
+    /// Returns `Some(None)` for the flag alone. A following argument is only taken as its value
+    /// if it is not another flag and `is_value` accepts it.
+    fn take_optional_value(
+        args: &mut Vec<OsString>,
+        key: &str,
+        is_value: impl Fn(&str) -> bool,
+    ) -> Option<Option<OsString>> {
+        let prefix = format!("{key}=");
+        let idx = args.iter().position(|arg| {
+            arg.to_str()
+                .is_some_and(|arg| arg == key || arg.starts_with(&prefix))
+        })?;
+
+        let arg = args.remove(idx);
+        if let Some(value) = arg.to_str().and_then(|arg| arg.strip_prefix(&prefix)) {
+            return Some(Some(value.into()));
+        }
+        let takes_next = args
+            .get(idx)
+            .and_then(|next| next.to_str())
+            .is_some_and(|next| !next.starts_with('-') && is_value(next));
+        Some(takes_next.then(|| args.remove(idx)))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn solve_options(args: &[&str]) -> solve::Options {
+            let args = args.iter().map(Into::into).collect();
+            match parse_from(pico_args::Arguments::from_vec(args)).unwrap() {
+                AppArguments::Solve { options, .. } => options,
+                _ => panic!("expected the solve command"),
+            }
+        }
+
+        #[test]
+        fn test_trace_path() {
+            let trace = |args: &[&str]| solve_options(args).trace;
+            let default = Some(PathBuf::from("data/traces/04.jsonl"));
+            let custom = Some(PathBuf::from("/tmp/out.jsonl"));
+
+            assert_eq!(trace(&["solve", "04"]), None);
+            assert_eq!(trace(&["solve", "04", "--trace"]), default);
+            assert_eq!(trace(&["solve", "--trace", "04"]), default);
+            assert_eq!(trace(&["solve", "04", "--trace", "--release"]), default);
+            assert_eq!(trace(&["solve", "04", "--trace", "/tmp/out.jsonl"]), custom);
+            assert_eq!(trace(&["solve", "--trace", "/tmp/out.jsonl", "04"]), custom);
+            assert_eq!(trace(&["solve", "04", "--trace=/tmp/out.jsonl"]), custom);
+        }
+
+        #[test]
+        fn test_verbose_level() {
+            assert_eq!(solve_options(&["solve", "01"]).verbose, 0);
+            assert_eq!(solve_options(&["solve", "01", "--verbose"]).verbose, 1);
+            assert_eq!(solve_options(&["solve", "01", "--verbose=2"]).verbose, 2);
+            assert_eq!(solve_options(&["solve", "--verbose=3", "01"]).verbose, 3);
+        }
+    }
 }
 
 fn main() {
@@ -103,7 +252,7 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::Time { day, options } => time::handle(day, &options),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -116,12 +265,15 @@ fn main() {
                     download::handle(day);
                 }
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+            AppArguments::Solve { day, options } => solve::handle(day, &options),
+            AppArguments::Replay { day, path } => replay::handle(day, path),
+            AppArguments::Gen { day, size, seed } => generate::handle(day, size, seed),
+            AppArguments::ProfileSummary { day, top } => {
+                profile::handle_summary(day, top.unwrap_or(profile::DEFAULT_TOP_SITES));
+            }
+            AppArguments::Verify => verify::handle(),
+            #[cfg(feature = "encrypted-inputs")]
+            AppArguments::Encrypt { day } => encrypt::handle(day),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
@@ -0,0 +1,263 @@
+/// Small zero-copy parsers for puzzle inputs.
+///
+/// The parsers for single values return `Result<_, String>`. [`Lines::parse`] runs such a
+/// parser on every line and adds the line number and text to the error message.
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+
+/// An error while parsing a line of input.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-based number of the offending line.
+    pub line: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {} in {:?}", self.line, self.message, self.text)
+    }
+}
+
+/// Same as [`Display`], so that `unwrap()` reports errors readably.
+impl std::fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/* -------------------------------------------------------------------------- */
+
+/// Some lines of input that know their position in the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lines<'a> {
+    text: &'a str,
+    first_line: usize,
+}
+
+impl<'a> Lines<'a> {
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The 1-based number of the first line.
+    pub fn first_line(&self) -> usize {
+        self.first_line
+    }
+
+    /// Iterates over the lines together with their 1-based line numbers.
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &'a str)> + use<'a> {
+        (self.first_line..).zip(self.text.lines())
+    }
+
+    /// Parses every line, failing with the position of the first line that could not be parsed.
+    pub fn parse<T>(&self, mut f: impl FnMut(&'a str) -> Result<T, String>) -> ParseResult<Vec<T>> {
+        self.numbered()
+            .map(|(line, text)| {
+                f(text).map_err(|message| ParseError {
+                    line,
+                    text: text.to_string(),
+                    message,
+                })
+            })
+            .collect()
+    }
+}
+
+/// All lines of an input.
+pub fn lines(input: &str) -> Lines<'_> {
+    Lines {
+        text: input,
+        first_line: 1,
+    }
+}
+
+/// Splits an input into blocks separated by blank lines, skipping leading and trailing blank lines.
+pub fn blocks(input: &str) -> impl Iterator<Item = Lines<'_>> {
+    let mut blocks = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (idx, line) in input.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+        match (start, is_blank) {
+            (None, false) => start = Some((idx + 1, offset)),
+            (Some((first_line, block_start)), true) => {
+                blocks.push(Lines {
+                    text: &input[block_start..offset],
+                    first_line,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+
+    if let Some((first_line, block_start)) = start {
+        blocks.push(Lines {
+            text: &input[block_start..],
+            first_line,
+        });
+    }
+
+    blocks.into_iter()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extracts all integers from some text, ignoring everything in between.
+/// A `-` directly in front of digits is a sign, unless it follows a letter or digit (as in `3-5`).
+/// Integers that do not fit into `T`, like negative ones for unsigned types, are skipped.
+///
+/// ```
+/// # use advent_of_code::parse::integers;
+/// let values: Vec<i32> = integers("p=0,-4 v=3-5").collect();
+/// assert_eq!(values, [0, -4, 3, 5]);
+/// ```
+pub fn integers<T: FromStr>(text: &str) -> impl Iterator<Item = T> + '_ {
+    let bytes = text.as_bytes();
+    let mut idx = 0;
+
+    std::iter::from_fn(move || {
+        while idx < bytes.len() {
+            let start = idx;
+            if !bytes[idx].is_ascii_digit() {
+                idx += 1;
+                continue;
+            }
+            while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+                idx += 1;
+            }
+
+            let is_negative = start > 0
+                && bytes[start - 1] == b'-'
+                && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric());
+            let signed_start = if is_negative { start - 1 } else { start };
+
+            if let Ok(value) = text[signed_start..idx].parse() {
+                return Some(value);
+            }
+        }
+        None
+    })
+}
+
+/// Parses a number, ignoring surrounding whitespace.
+pub fn number<T: FromStr>(text: &str) -> Result<T, String> {
+    text.trim()
+        .parse()
+        .map_err(|_| format!("expected a number, found {:?}", text.trim()))
+}
+
+/// Splits at the first occurrence of a delimiter.
+pub fn split_once<'a>(text: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), String> {
+    text.split_once(delimiter)
+        .ok_or_else(|| format!("expected {delimiter:?}"))
+}
+
+/// Parses an inclusive range written as `a-b`.
+pub fn range<T: FromStr>(text: &str) -> Result<RangeInclusive<T>, String> {
+    let text = text.trim();
+    // skip the first character, which may be the sign of the start
+    let dash = text
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c == '-')
+        .map(|(idx, _)| idx)
+        .ok_or_else(|| format!("expected a range like \"a-b\", found {text:?}"))?;
+
+    Ok(number(&text[..dash])?..=number(&text[dash + 1..])?)
+}
+
+/// Parses every part of a list separated by a delimiter.
+pub fn separated<'a, T>(
+    text: &'a str,
+    delimiter: char,
+    f: impl FnMut(&'a str) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    text.trim().split(delimiter).map(f).collect()
+}
+
+/// Parses a fixed number of comma-separated numbers, like `1,2,3`.
+pub fn tuple<T: FromStr, const N: usize>(text: &str) -> Result<[T; N], String> {
+    let values = separated(text, ',', number)?;
+    let len = values.len();
+    values
+        .try_into()
+        .map_err(|_| format!("expected {N} comma-separated values, found {len}"))
+}
+
+/// Strips enclosing delimiters, like the brackets of `[1,2]`.
+pub fn bracketed(text: &str, open: char, close: char) -> Result<&str, String> {
+    text.trim()
+        .strip_prefix(open)
+        .and_then(|rest| rest.strip_suffix(close))
+        .ok_or_else(|| format!("expected {:?} to be enclosed in {open}{close}", text.trim()))
+}
+
+/// Parses a comma-separated list of numbers in delimiters, like `[1,2,3]` or `{4,5}`.
+pub fn bracketed_list<T: FromStr>(text: &str, open: char, close: char) -> Result<Vec<T>, String> {
+    separated(bracketed(text, open, close)?, ',', number)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocks, bracketed, bracketed_list, integers, lines, number, range, tuple};
+
+    #[test]
+    fn extracts_integers() {
+        let values: Vec<i64> = integers("x=-12, y=7..-3 a-1 3-5").collect();
+        assert_eq!(values, [-12, 7, -3, 1, 3, 5]);
+
+        let values: Vec<u8> = integers("-1 300 2").collect();
+        assert_eq!(values, [2]);
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "\n1-3\n5-8\n\n\n1\n2\n";
+        let blocks: Vec<_> = blocks(input).collect();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].text(), "1-3\n5-8\n");
+        assert_eq!(blocks[0].first_line(), 2);
+        assert_eq!(blocks[1].text(), "1\n2\n");
+        assert_eq!(blocks[1].first_line(), 6);
+    }
+
+    #[test]
+    fn parses_values() {
+        assert_eq!(number::<u32>(" 42 "), Ok(42));
+        assert_eq!(range::<i32>("-5-10"), Ok(-5..=10));
+        assert!(range::<u32>("5").is_err());
+        assert_eq!(tuple::<u32, 3>("1,2,3"), Ok([1, 2, 3]));
+        assert!(tuple::<u32, 2>("1,2,3").is_err());
+        assert_eq!(bracketed("(1,3)", '(', ')'), Ok("1,3"));
+        assert_eq!(
+            bracketed_list::<u16>("{3,5,4,7}", '{', '}'),
+            Ok(vec![3, 5, 4, 7])
+        );
+        assert!(bracketed_list::<u16>("[3,5", '[', ']').is_err());
+    }
+
+    #[test]
+    fn reports_line_numbers() {
+        let error = blocks("1\n2\n\n3\nx\n")
+            .nth(1)
+            .unwrap()
+            .parse(number::<u32>)
+            .unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(
+            error.to_string(),
+            "line 5: expected a number, found \"x\" in \"x\""
+        );
+        assert_eq!(lines("1\n2\n").parse(number::<u32>), Ok(vec![1, 2]));
+    }
+}
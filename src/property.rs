@@ -0,0 +1,448 @@
+/// Property tests that compare an optimised implementation with a naive reference on random inputs.
+///
+/// [`check`] generates inputs from a seeded [`Rng`], and if the implementations disagree, shrinks the
+/// input to a small one that still shows the difference before failing. The seed can be set with the
+/// `AOC_PROPERTY_SEED` environment variable and the number of cases with `AOC_PROPERTY_CASES`.
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::{env, iter};
+
+use crate::geometry::Scalar;
+use crate::grid::Grid;
+use crate::rng::Rng;
+
+const DEFAULT_SEED: u64 = 2025;
+const DEFAULT_CASES: usize = 256;
+
+/// Settings of a property test.
+#[derive(Clone, Copy, Debug)]
+pub struct Config<T> {
+    pub seed: u64,
+    pub cases: usize,
+    /// The maximum number of candidates tried while shrinking.
+    pub max_shrinks: usize,
+    /// Inputs the implementations are defined for. Shrinking never leaves them.
+    pub valid: fn(&T) -> bool,
+}
+
+impl<T> Default for Config<T> {
+    fn default() -> Self {
+        fn from_env<V: FromStr>(key: &str) -> Option<V> {
+            env::var(key).ok().and_then(|value| value.parse().ok())
+        }
+
+        Self {
+            seed: from_env("AOC_PROPERTY_SEED").unwrap_or(DEFAULT_SEED),
+            cases: from_env("AOC_PROPERTY_CASES").unwrap_or(DEFAULT_CASES),
+            max_shrinks: 10_000,
+            valid: |_| true,
+        }
+    }
+}
+
+impl<T> Config<T> {
+    #[must_use]
+    pub fn valid(self, valid: fn(&T) -> bool) -> Self {
+        Self { valid, ..self }
+    }
+
+    #[must_use]
+    pub fn cases(self, cases: usize) -> Self {
+        Self { cases, ..self }
+    }
+}
+
+/// Checks that two implementations agree on generated inputs, with the default [`Config`].
+///
+/// # Panics
+/// With the smallest input found on which the implementations differ.
+///
+/// ```
+/// # use advent_of_code::property::{check, numbers};
+/// check(
+///     |rng| numbers(rng, 0..=20, 0..=100u64),
+///     |values| values.iter().max().copied(),
+///     |values| values.iter().copied().reduce(u64::max),
+/// );
+/// ```
+pub fn check<T, R>(
+    generate: impl Fn(&mut Rng) -> T,
+    naive: impl Fn(&T) -> R,
+    optimized: impl Fn(&T) -> R,
+) where
+    T: Shrink + Debug,
+    R: PartialEq + Debug,
+{
+    check_with(&Config::default(), generate, naive, optimized);
+}
+
+/// Like [`check`], with custom settings.
+///
+/// # Panics
+/// With the smallest input found on which the implementations differ.
+pub fn check_with<T, R>(
+    config: &Config<T>,
+    generate: impl Fn(&mut Rng) -> T,
+    naive: impl Fn(&T) -> R,
+    optimized: impl Fn(&T) -> R,
+) where
+    T: Shrink + Debug,
+    R: PartialEq + Debug,
+{
+    let mut rng = Rng::new(config.seed);
+    let differs = |input: &T| naive(input) != optimized(input);
+
+    for case in 1..=config.cases {
+        let input = generate(&mut rng);
+        if !(config.valid)(&input) || !differs(&input) {
+            continue;
+        }
+
+        let (input, shrinks) = shrink(input, config, differs);
+        panic!(
+            "implementations differ on case {case} (seed {}, shrunk {shrinks} times)\n\
+             input: {input:?}\nnaive: {:?}\noptimized: {:?}",
+            config.seed,
+            naive(&input),
+            optimized(&input)
+        );
+    }
+}
+
+/// Replaces the input by smaller ones that still fail, until none of its shrinks fails.
+/// Returns the smallest input and the number of successful shrinks.
+fn shrink<T: Shrink>(mut input: T, config: &Config<T>, fails: impl Fn(&T) -> bool) -> (T, usize) {
+    let mut tries = 0;
+    let mut shrinks = 0;
+
+    'shrink: while tries < config.max_shrinks {
+        for candidate in input.shrink() {
+            if !(config.valid)(&candidate) {
+                continue;
+            }
+            tries += 1;
+            if fails(&candidate) {
+                input = candidate;
+                shrinks += 1;
+                continue 'shrink;
+            }
+            if tries >= config.max_shrinks {
+                break;
+            }
+        }
+        break;
+    }
+
+    (input, shrinks)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Values that can be simplified to find a minimal failing input.
+pub trait Shrink: Sized {
+    /// Smaller variants of the value, most aggressive first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! impl_shrink_int {
+    ($( $t:ty ),*) => {
+        $(
+            impl Shrink for $t {
+                /// Moves towards zero.
+                fn shrink(&self) -> Vec<Self> {
+                    let value = *self;
+                    let mut candidates = vec![];
+                    if value != 0 {
+                        candidates.push(0);
+                        candidates.push(value / 2);
+                        candidates.push(if value > 0 { value - 1 } else { value + 1 });
+                    }
+                    candidates.dedup();
+                    candidates.retain(|candidate| *candidate != value);
+                    candidates
+                }
+            }
+        )*
+    };
+}
+
+impl_shrink_int!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+impl Shrink for bool {
+    fn shrink(&self) -> Vec<Self> {
+        if *self { vec![false] } else { vec![] }
+    }
+}
+
+impl Shrink for char {
+    fn shrink(&self) -> Vec<Self> {
+        vec![]
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b) = self;
+        iter::empty()
+            .chain(a.shrink().into_iter().map(|a| (a, b.clone())))
+            .chain(b.shrink().into_iter().map(|b| (a.clone(), b)))
+            .collect()
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone, C: Shrink + Clone> Shrink for (A, B, C) {
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b, c) = self;
+        iter::empty()
+            .chain(a.shrink().into_iter().map(|a| (a, b.clone(), c.clone())))
+            .chain(b.shrink().into_iter().map(|b| (a.clone(), b, c.clone())))
+            .chain(c.shrink().into_iter().map(|c| (a.clone(), b.clone(), c)))
+            .collect()
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    /// Drops halves, then single elements, then shrinks single elements.
+    fn shrink(&self) -> Vec<Self> {
+        let len = self.len();
+        let mut candidates = vec![];
+
+        if len > 1 {
+            candidates.push(self[..len / 2].to_vec());
+            candidates.push(self[len / 2..].to_vec());
+        }
+        for idx in 0..len {
+            let mut candidate = self.clone();
+            candidate.remove(idx);
+            candidates.push(candidate);
+        }
+        for (idx, item) in self.iter().enumerate() {
+            for smaller in item.shrink() {
+                let mut candidate = self.clone();
+                candidate[idx] = smaller;
+                candidates.push(candidate);
+            }
+        }
+
+        candidates
+    }
+}
+
+impl<T: Shrink + Scalar> Shrink for RangeInclusive<T> {
+    /// Shrinks either end, keeping the range non-empty.
+    fn shrink(&self) -> Vec<Self> {
+        let (start, end) = (*self.start(), *self.end());
+        iter::empty()
+            .chain(end.shrink().into_iter().map(|end| start..=end))
+            .chain(start.shrink().into_iter().map(|start| start..=end))
+            .filter(|range| range.start() <= range.end())
+            .collect()
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Grid<T> {
+    /// Drops the last row or column, then shrinks single cells.
+    fn shrink(&self) -> Vec<Self> {
+        let (width, height) = (self.width(), self.height());
+        let mut candidates = vec![];
+
+        if height > 1 {
+            let cells = self.rows().take(height - 1).flatten().cloned().collect();
+            candidates.push(Grid::new(width, height - 1, cells));
+        }
+        if width > 1 {
+            let cells = self
+                .rows()
+                .flat_map(|row| &row[..width - 1])
+                .cloned()
+                .collect();
+            candidates.push(Grid::new(width - 1, height, cells));
+        }
+        for (pos, cell) in self.iter() {
+            for smaller in cell.shrink() {
+                let mut candidate = self.clone();
+                candidate[pos] = smaller;
+                candidates.push(candidate);
+            }
+        }
+
+        candidates
+    }
+}
+
+impl Shrink for String {
+    /// Drops lines, like a `Vec` of lines. Meant for generated puzzle inputs whose lines
+    /// stand on their own; inputs that may not lose lines need a [`Config::valid`] check.
+    fn shrink(&self) -> Vec<Self> {
+        let lines: Vec<&str> = self.lines().collect();
+        let len = lines.len();
+        let join = |lines: &[&str]| lines.iter().map(|line| format!("{line}\n")).collect();
+        let mut candidates = vec![];
+
+        if len > 1 {
+            candidates.push(join(&lines[..len / 2]));
+            candidates.push(join(&lines[len / 2..]));
+        }
+        for idx in 0..len {
+            let mut candidate = lines.clone();
+            candidate.remove(idx);
+            candidates.push(join(&candidate));
+        }
+
+        candidates
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A list of numbers.
+pub fn numbers<T: Scalar>(
+    rng: &mut Rng,
+    len: RangeInclusive<usize>,
+    values: RangeInclusive<T>,
+) -> Vec<T> {
+    let len = rng.range(len);
+    (0..len).map(|_| rng.range(values.clone())).collect()
+}
+
+/// A list of non-empty ranges inside `bounds` that are at most `max_len` long. They may overlap.
+///
+/// # Panics
+/// If `max_len` is not positive, as no non-empty range is that short.
+pub fn ranges<T: Scalar>(
+    rng: &mut Rng,
+    len: RangeInclusive<usize>,
+    bounds: RangeInclusive<T>,
+    max_len: T,
+) -> Vec<RangeInclusive<T>> {
+    assert!(
+        max_len > T::ZERO,
+        "ranges must be allowed to be at least 1 long"
+    );
+    let len = rng.range(len);
+    (0..len)
+        .map(|_| {
+            let start = rng.range(bounds.clone());
+            let longest = (*bounds.end() - start).min(max_len - T::ONE);
+            start..=start + rng.range(T::ZERO..=longest)
+        })
+        .collect()
+}
+
+/// A grid whose cells are generated one by one.
+pub fn grid<T>(
+    rng: &mut Rng,
+    width: RangeInclusive<usize>,
+    height: RangeInclusive<usize>,
+    mut cell: impl FnMut(&mut Rng) -> T,
+) -> Grid<T> {
+    let (width, height) = (rng.range(width), rng.range(height));
+    let cells = (0..width * height).map(|_| cell(rng)).collect();
+    Grid::new(width, height, cells)
+}
+
+/// The edges of a directed graph on `nodes` nodes, each present with a given probability.
+/// If `acyclic` is set, edges only lead from lower to higher node ids.
+pub fn edges(
+    rng: &mut Rng,
+    nodes: RangeInclusive<usize>,
+    probability: f64,
+    acyclic: bool,
+) -> Vec<(usize, usize)> {
+    let nodes = rng.range(nodes);
+    let mut edges = vec![];
+    for from in 0..nodes {
+        for to in 0..nodes {
+            let allowed = if acyclic { from < to } else { from != to };
+            if allowed && rng.chance(probability) {
+                edges.push((from, to));
+            }
+        }
+    }
+    edges
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::panic;
+
+    use super::{Config, Shrink, check, check_with, edges, grid, numbers, ranges};
+    use crate::rng::Rng;
+
+    #[test]
+    fn shrinks_values() {
+        assert_eq!(10u32.shrink(), [0, 5, 9]);
+        assert_eq!((-3i8).shrink(), [0, -1, -2]);
+        assert!(0u8.shrink().is_empty());
+        assert_eq!((2..=3u8).shrink(), [2..=2, 0..=3, 1..=3]);
+        assert!(vec![1u8, 2, 3].shrink().contains(&vec![1, 3]));
+        assert!(
+            String::from("a\nb\nc\n")
+                .shrink()
+                .contains(&"a\nc\n".to_string())
+        );
+    }
+
+    #[test]
+    fn passes_equal_implementations() {
+        check(
+            |rng| numbers(rng, 0..=10, 0..=1000u32),
+            |values| values.iter().sum::<u32>(),
+            |values| values.iter().rev().sum::<u32>(),
+        );
+    }
+
+    #[test]
+    fn finds_minimal_counterexample() {
+        let result = panic::catch_unwind(|| {
+            check_with(
+                &Config::default().valid(|values: &Vec<u32>| values.len() < 50),
+                |rng| numbers(rng, 0..=20, 0..=1000u32),
+                |values| values.iter().max().copied(),
+                // wrong if the maximum is at the end
+                |values| {
+                    values
+                        .iter()
+                        .take(values.len().saturating_sub(1))
+                        .max()
+                        .copied()
+                },
+            );
+        });
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("input: [0]\n"), "{message}");
+    }
+
+    #[test]
+    fn generates_inputs() {
+        let mut rng = Rng::new(3);
+        for _ in 0..50 {
+            for range in ranges(&mut rng, 1..=5, 10..=20u64, 3) {
+                assert!(range.start() <= range.end());
+                assert!(*range.start() >= 10 && *range.end() <= 20);
+                assert!(range.end() - range.start() < 3);
+            }
+
+            let grid = grid(&mut rng, 1..=4, 2..=3, |rng| rng.chance(0.5));
+            assert!((1..=4).contains(&grid.width()) && (2..=3).contains(&grid.height()));
+
+            assert!(
+                edges(&mut rng, 0..=6, 0.5, true)
+                    .iter()
+                    .all(|(from, to)| from < to)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_zero_max_len() {
+        let result = panic::catch_unwind(|| ranges(&mut Rng::new(1), 1..=1, 0..=9u64, 0));
+        let message = *result.unwrap_err().downcast::<&str>().unwrap();
+        assert_eq!(message, "ranges must be allowed to be at least 1 long");
+    }
+}
@@ -0,0 +1,134 @@
+/// A small, seedable pseudo-random number generator for generated inputs and property tests.
+use std::ops::RangeInclusive;
+
+use crate::geometry::Scalar;
+
+/// SplitMix64: fast, with good enough statistics, and the same sequence for a seed on every platform.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed value in `0..n`.
+    ///
+    /// # Panics
+    /// If `n` is zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot sample from an empty range");
+        // reject the values that would make the lowest remainders more likely
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % n;
+            }
+        }
+    }
+
+    /// A uniformly distributed value in an inclusive range.
+    ///
+    /// # Panics
+    /// If the range is empty.
+    pub fn range<T: Scalar>(&mut self, range: RangeInclusive<T>) -> T {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "cannot sample from an empty range");
+        // the difference modulo 2^128 is the span even where `as_i128` wraps or `end - start`
+        // overflows, as for `0..=u128::MAX` or `i128::MIN..=i128::MAX`
+        #[allow(clippy::cast_sign_loss)]
+        let span = end.as_i128().wrapping_sub(start.as_i128()) as u128;
+
+        let offset = match span.checked_add(1).map(u64::try_from) {
+            Some(Ok(span)) => u128::from(self.below(span)),
+            Some(Err(_)) => self.next_u128() % (span + 1),
+            // the full range of a 128-bit type
+            None => self.next_u128(),
+        };
+        #[allow(clippy::cast_possible_wrap)]
+        T::from_i128(start.as_i128().wrapping_add(offset as i128))
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        u128::from(self.next_u64()) << 64 | u128::from(self.next_u64())
+    }
+
+    /// `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        #[allow(clippy::cast_precision_loss)]
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+
+    /// A uniformly chosen element.
+    ///
+    /// # Panics
+    /// If the slice is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    /// Shuffles a slice in place (Fisher–Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.range(0..=idx));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let values: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(values, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(Rng::new(43).next_u64(), values[0]);
+    }
+
+    #[test]
+    fn samples_ranges() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 7];
+        for _ in 0..200 {
+            let value = rng.range(-3i32..=3);
+            seen[(value + 3) as usize] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+
+        assert_eq!(rng.range(5u8..=5), 5);
+        let big = rng.range(0u128..=u128::MAX >> 1);
+        assert!(big <= u128::MAX >> 1);
+        let top = rng.range(u128::MAX - 1..=u128::MAX);
+        assert!(top >= u128::MAX - 1);
+        rng.range(0u128..=u128::MAX);
+        rng.range(i128::MIN..=i128::MAX);
+        assert!(rng.range(-1i128..=i128::MAX) >= -1);
+    }
+
+    #[test]
+    fn shuffles() {
+        let mut rng = Rng::new(1);
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
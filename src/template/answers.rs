@@ -0,0 +1,302 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the recorded answers of a single day for a single named input.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: Day,
+    pub input: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// SHA-256 of the input at the time the answers were recorded.
+    pub input_sha256: Option<String>,
+}
+
+/// Represents recorded answers for a set of days and inputs.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Look up the recorded answer for one part of a day's named input.
+    pub fn get(&self, day: Day, input: &str, part: u8) -> Option<&str> {
+        let answer = self
+            .data
+            .iter()
+            .find(|a| a.day == day && a.input == input)?;
+
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Look up the checksum of the input the answers of a day's named input were recorded for.
+    pub fn input_sha256(&self, day: Day, input: &str) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|a| a.day == day && a.input == input)?
+            .input_sha256
+            .as_deref()
+    }
+
+    /// Record the answer for one part of a day's named input, overwriting a previous value.
+    pub fn set(&mut self, day: Day, input: &str, part: u8, value: String) {
+        let answer = self.entry(day, input);
+
+        match part {
+            1 => answer.part_1 = Some(value),
+            2 => answer.part_2 = Some(value),
+            _ => {}
+        }
+    }
+
+    /// Record the checksum of the input the answers of a day's named input belong to.
+    pub fn set_input_sha256(&mut self, day: Day, input: &str, sha256: String) {
+        self.entry(day, input).input_sha256 = Some(sha256);
+    }
+
+    fn entry(&mut self, day: Day, input: &str) -> &mut Answer {
+        if !self.data.iter().any(|a| a.day == day && a.input == input) {
+            self.data.push(Answer {
+                day,
+                input: input.to_string(),
+                part_1: None,
+                part_2: None,
+                input_sha256: None,
+            });
+            self.data
+                .sort_by(|a, b| a.day.cmp(&b.day).then_with(|| a.input.cmp(&b.input)));
+        }
+
+        self.data
+            .iter_mut()
+            .find(|a| a.day == day && a.input == input)
+            .unwrap()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("input".into(), JsonValue::String(value.input.clone()));
+
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        if let Some(sha256) = &value.input_sha256 {
+            map.insert("input_sha256".into(), JsonValue::String(sha256.clone()));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let input = json
+            .get("input")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.input to be a string.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        // NOTE: optional, answers recorded without a checksum skip the change detection.
+        let input_sha256 = json.get("input_sha256").and_then(|v| v.get::<String>());
+
+        Ok(Answer {
+            day,
+            input: input.clone(),
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+            input_sha256: input_sha256.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Answer, Answers};
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    input: "default".into(),
+                    part_1: Some("3".into()),
+                    part_2: Some("6".into()),
+                    input_sha256: None,
+                },
+                Answer {
+                    day: day!(1),
+                    input: "alice".into(),
+                    part_1: Some("1042".into()),
+                    part_2: None,
+                    input_sha256: Some("abc".into()),
+                },
+            ],
+        }
+    }
+
+    mod deserialization {
+        use crate::{day, template::answers::Answers};
+
+        #[test]
+        fn handles_json_answers() {
+            let json =
+                r#"{ "data": [{ "day": "01", "input": "bob", "part_1": "42", "part_2": null }] }"#
+                    .to_string();
+            let answers = Answers::try_from(json).unwrap();
+            assert_eq!(answers.data.len(), 1);
+            let answer = answers.data.first().unwrap();
+            assert_eq!(answer.day, day!(1));
+            assert_eq!(answer.input, "bob");
+            assert_eq!(answer.part_1, Some("42".to_string()));
+            assert_eq!(answer.part_2, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_missing_input_name() {
+            let json =
+                r#"{ "data": [{ "day": "01", "part_1": "42", "part_2": null }] }"#.to_string();
+            Answers::try_from(json).unwrap();
+        }
+    }
+
+    mod lookup {
+        use super::get_mock_answers;
+        use crate::day;
+
+        #[test]
+        fn gets_answers_by_input_name() {
+            let answers = get_mock_answers();
+            assert_eq!(answers.get(day!(1), "default", 2), Some("6"));
+            assert_eq!(answers.get(day!(1), "alice", 1), Some("1042"));
+            assert_eq!(answers.get(day!(1), "alice", 2), None);
+            assert_eq!(answers.get(day!(2), "default", 1), None);
+        }
+
+        #[test]
+        fn sets_new_and_existing_answers() {
+            let mut answers = get_mock_answers();
+            answers.set(day!(1), "alice", 2, "7".into());
+            answers.set(day!(2), "default", 1, "8".into());
+            assert_eq!(answers.data.len(), 3);
+            assert_eq!(answers.get(day!(1), "alice", 1), Some("1042"));
+            assert_eq!(answers.get(day!(1), "alice", 2), Some("7"));
+            assert_eq!(answers.get(day!(2), "default", 1), Some("8"));
+        }
+
+        #[test]
+        fn sets_input_checksums() {
+            let mut answers = get_mock_answers();
+            answers.set_input_sha256(day!(1), "default", "def".into());
+            assert_eq!(answers.input_sha256(day!(1), "default"), Some("def"));
+            assert_eq!(answers.input_sha256(day!(1), "alice"), Some("abc"));
+            assert_eq!(answers.input_sha256(day!(2), "default"), None);
+        }
+    }
+}
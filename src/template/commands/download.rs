@@ -1,4 +1,4 @@
-use crate::template::{Day, aoc_cli};
+use crate::template::{Day, aoc_cli, integrity};
 use std::process;
 
 pub fn handle(day: Day) {
@@ -13,4 +13,9 @@ pub fn handle(day: Day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    if let Err(e) = integrity::record_download(day) {
+        eprintln!("failed to record input checksum: {e}");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,32 @@
+use std::{path::Path, process};
+
+use crate::template::{Day, all_days, encryption, input_names, integrity};
+
+pub fn handle(day: Option<Day>) {
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+
+    for day in days {
+        for name in input_names(day) {
+            let path = integrity::input_path(day, &name);
+
+            // skip inputs that are only present in encrypted form.
+            if !Path::new(&path).exists() {
+                continue;
+            }
+
+            match encryption::encrypt_file(&path) {
+                Ok(target) => {
+                    println!(
+                        "Encrypted \"{}\" to \"{}\"",
+                        path.display(),
+                        target.display()
+                    );
+                }
+                Err(e) => {
+                    eprintln!("Failed to encrypt \"{}\": {e}", path.display());
+                    process::exit(1);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::{DEFAULT_GENERATED_SEED, DEFAULT_GENERATED_SIZE, Day};
+
+/// Prints an input generated by the day's `generate` function.
+pub fn handle(day: Day, size: Option<usize>, seed: Option<u64>) {
+    let size = size.unwrap_or(DEFAULT_GENERATED_SIZE);
+    let seed = seed.unwrap_or(DEFAULT_GENERATED_SEED);
+
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &day.to_string(),
+            "--",
+        ])
+        .args(["--generate", "--size", &size.to_string()])
+        .args(["--seed", &seed.to_string()])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -1,6 +1,12 @@
 pub mod all;
 pub mod download;
+#[cfg(feature = "encrypted-inputs")]
+pub mod encrypt;
+pub mod generate;
+pub mod profile;
 pub mod read;
+pub mod replay;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
@@ -0,0 +1,67 @@
+use std::process;
+
+use crate::template::memory::format_bytes;
+use crate::template::profiles::{self, DhatProfile};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, all_days};
+
+/// Number of allocation sites listed per profile if `--top` is not passed.
+pub const DEFAULT_TOP_SITES: usize = 10;
+
+/// Prints the top allocation sites of the DHAT profiles written by `cargo solve <day> --dhat`.
+pub fn handle_summary(day: Option<Day>, top: usize) {
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+    let paths: Vec<_> = days
+        .iter()
+        .flat_map(|day| [1, 2].map(|part| (*day, part, profiles::dhat_path(*day, part))))
+        .filter(|(_, _, path)| path.is_file())
+        .collect();
+
+    if paths.is_empty() {
+        let day = day.map_or_else(|| "<day>".to_string(), |day| day.to_string());
+        eprintln!(
+            "No DHAT profiles found in {}. Record them with `cargo solve {day} --dhat`.",
+            profiles::dir().display()
+        );
+        process::exit(1);
+    }
+
+    for (idx, (day, part, path)) in paths.iter().enumerate() {
+        if idx > 0 {
+            println!();
+        }
+
+        let profile = match DhatProfile::read_file(path) {
+            Ok(profile) => profile,
+            Err(e) => {
+                eprintln!("Failed to read profile: {e}");
+                process::exit(1);
+            }
+        };
+
+        println!(
+            "{ANSI_BOLD}Day {day} · Part {part}{ANSI_RESET} {ANSI_ITALIC}({}){ANSI_RESET}",
+            path.display()
+        );
+        println!(
+            "Total: {} in {} blocks",
+            format_bytes(profile.total_bytes()),
+            profile.total_blocks()
+        );
+
+        for site in profile.sites.iter().take(top) {
+            println!(
+                "{:>10} {:>8} blocks  {:>10} max  {}",
+                format_bytes(site.total_bytes),
+                site.total_blocks,
+                format_bytes(site.max_bytes),
+                site.location()
+            );
+        }
+        if profile.sites.len() > top {
+            println!(
+                "{ANSI_ITALIC}… {} more sites{ANSI_RESET}",
+                profile.sites.len() - top
+            );
+        }
+    }
+}
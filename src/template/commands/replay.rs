@@ -0,0 +1,138 @@
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::trace::{self, Frame};
+
+/// Number of frames skipped by the up and down keys.
+const JUMP: usize = 10;
+
+enum Key {
+    Next,
+    Previous,
+    JumpForward,
+    JumpBack,
+    First,
+    Last,
+    Quit,
+    Other,
+}
+
+pub fn handle(day: Day, path: Option<PathBuf>) {
+    let path = path.unwrap_or_else(|| trace::default_path(day));
+
+    let frames = match trace::read_file(&path) {
+        Ok(frames) if frames.is_empty() => {
+            eprintln!("No frames recorded in {}.", path.display());
+            process::exit(1);
+        }
+        Ok(frames) => frames,
+        Err(e) => {
+            eprintln!("Failed to read trace: {e}");
+            eprintln!("Record one with `cargo solve {day} --trace`.");
+            process::exit(1);
+        }
+    };
+
+    let _terminal = RawTerminal::enable();
+    let mut stdin = io::stdin().lock();
+    let last = frames.len() - 1;
+    let mut current = 0;
+
+    loop {
+        draw(day, &frames, current);
+
+        current = match read_key(&mut stdin) {
+            Key::Next => (current + 1).min(last),
+            Key::Previous => current.saturating_sub(1),
+            Key::JumpForward => (current + JUMP).min(last),
+            Key::JumpBack => current.saturating_sub(JUMP),
+            Key::First => 0,
+            Key::Last => last,
+            Key::Quit => break,
+            Key::Other => current,
+        };
+    }
+}
+
+fn draw(day: Day, frames: &[Frame], current: usize) {
+    let frame = &frames[current];
+    let mut stdout = io::stdout().lock();
+    let _ = write!(
+        stdout,
+        "\x1b[2J\x1b[H{ANSI_BOLD}Day {day} · Part {} · frame {}{ANSI_RESET} ({}/{})\n\
+         {ANSI_ITALIC}←/→ step · ↑/↓ jump {JUMP} · g/G first/last · q quit{ANSI_RESET}\n\n{}\n",
+        frame.part,
+        frame.index,
+        current + 1,
+        frames.len(),
+        frame.state
+    );
+    let _ = stdout.flush();
+}
+
+fn read_key(stdin: &mut impl Read) -> Key {
+    let mut next_byte = || {
+        let mut byte = [0];
+        stdin.read_exact(&mut byte).ok().map(|()| byte[0])
+    };
+
+    match next_byte() {
+        // Ctrl-C arrives as a byte, as signals are off while the terminal is raw
+        None | Some(b'q' | 0x03) => Key::Quit,
+        Some(b'l' | b' ' | b'n') => Key::Next,
+        Some(b'h' | b'p') => Key::Previous,
+        Some(b'g') => Key::First,
+        Some(b'G') => Key::Last,
+        Some(0x1b) => match (next_byte(), next_byte()) {
+            (Some(b'['), Some(b'C')) => Key::Next,
+            (Some(b'['), Some(b'D')) => Key::Previous,
+            (Some(b'['), Some(b'A')) => Key::JumpBack,
+            (Some(b'['), Some(b'B')) => Key::JumpForward,
+            _ => Key::Other,
+        },
+        Some(_) => Key::Other,
+    }
+}
+
+/// Reads keys without waiting for enter while alive, by switching the terminal with `stty`.
+/// Ctrl-C does not send a signal meanwhile, so the terminal is always restored on drop.
+/// If stdin is not a terminal, keys are read line by line instead.
+struct RawTerminal {
+    saved: Option<String>,
+}
+
+impl RawTerminal {
+    fn enable() -> Self {
+        let saved = stty(&["-g"])
+            .filter(|_| stty(&["-icanon", "-echo", "-isig", "min", "1"]).is_some())
+            .map(|settings| settings.trim().to_string());
+        print!("\x1b[?25l");
+        Self { saved }
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h");
+        if let Some(saved) = &self.saved {
+            stty(&[saved]);
+        }
+    }
+}
+
+/// Runs `stty` on the terminal attached to stdin and returns its output.
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
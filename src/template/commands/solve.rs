@@ -1,8 +1,44 @@
-use std::process::{Command, Stdio};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
 
 use crate::template::Day;
+use crate::viz;
+
+/// Flags of `cargo solve`, most of which are passed on to the solution binary.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub dhat: bool,
+    /// Sample the call stack while benching and write a flame graph.
+    pub profile: bool,
+    pub submit_part: Option<u8>,
+    pub all_inputs: bool,
+    pub record: bool,
+    pub visualize: Option<viz::Output>,
+    /// Verbosity level for `debug!` output, 0 if disabled.
+    pub verbose: u8,
+    /// File to record `trace::frame` calls to.
+    pub trace: Option<PathBuf>,
+}
+
+pub fn handle(day: Day, options: &Options) {
+    let Options {
+        release,
+        dhat,
+        profile,
+        submit_part,
+        all_inputs,
+        record,
+        visualize,
+        verbose,
+        ref trace,
+    } = *options;
+
+    if dhat && profile {
+        eprintln!("`--dhat` and `--profile` cannot be combined.");
+        process::exit(1);
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,10 +48,21 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
+    } else if profile {
+        cmd_args.extend([
+            "--profile".to_string(),
+            "profiling".to_string(),
+            "--features".to_string(),
+            "cpu-profile".to_string(),
+        ]);
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    if visualize == Some(viz::Output::Png) {
+        cmd_args.extend(["--features".to_string(), "png-frames".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +70,34 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if all_inputs {
+        cmd_args.push("--all-inputs".to_string());
+    }
+
+    if record {
+        cmd_args.push("--record".to_string());
+    }
+
+    if profile {
+        cmd_args.push("--profile".to_string());
+    }
+
+    if verbose > 0 {
+        cmd_args.push(format!("--verbose={verbose}"));
+    }
+
+    if let Some(path) = trace {
+        cmd_args.push(format!("--trace={}", path.display()));
+    }
+
+    if let Some(output) = visualize {
+        cmd_args.extend([
+            "--visualize".to_string(),
+            "--frames".to_string(),
+            output.to_string(),
+        ]);
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +105,9 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -1,10 +1,50 @@
 use std::collections::HashSet;
+use std::process::{self, Command, Stdio};
 
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{DEFAULT_GENERATED_SEED, Day, all_days, machine, readme_benchmarks};
+
+/// Flags of `cargo time`.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub run_all: bool,
+    pub store: bool,
+    pub scale: bool,
+    pub seed: Option<u64>,
+    pub track_memory: bool,
+    pub count_events: bool,
+    /// Pin the benchmarking thread to a core.
+    pub pin: bool,
+    /// The core to pin to, implies `pin`. Defaults to `machine::default_core`.
+    pub core: Option<usize>,
+}
+
+pub fn handle(day: Option<Day>, options: &Options) {
+    let Options {
+        run_all,
+        store,
+        scale,
+        seed,
+        track_memory,
+        count_events,
+        pin,
+        core,
+    } = *options;
+    let pin = core.or(pin.then(machine::default_core));
+
+    for warning in machine::frequency_warnings() {
+        eprintln!("Warning: {warning}");
+    }
+
+    if scale {
+        let Some(day) = day else {
+            eprintln!("`--scale` needs a day, e.g. `cargo time 8 --scale`.");
+            process::exit(1);
+        };
+        return run_scaling(day, seed, pin);
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,7 +61,13 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(&days_to_run, true, true, track_memory, count_events, pin).unwrap();
+
+    let (cpu_model, rustc_version) = (machine::cpu_model(), machine::rustc_version());
+    for timing in &mut timings.data {
+        timing.cpu_model.clone_from(&cpu_model);
+        timing.rustc_version.clone_from(&rustc_version);
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -38,3 +84,25 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Times the day's solution on generated inputs of growing size, see `runner::scale_part`.
+fn run_scaling(day: Day, seed: Option<u64>, pin: Option<usize>) {
+    let seed = seed.unwrap_or(DEFAULT_GENERATED_SEED);
+
+    let status = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &day.to_string()])
+        .args(["--", "--scale", "--seed", &seed.to_string()])
+        .args(
+            pin.map(|core| ["--pin".to_string(), core.to_string()])
+                .into_iter()
+                .flatten(),
+        )
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
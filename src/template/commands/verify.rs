@@ -0,0 +1,51 @@
+use std::{
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET, all_days, input_names, run_multi::get_path_for_bin};
+
+/// Runs every scaffolded solution against all of its inputs and compares the results with the recorded answers.
+pub fn handle() {
+    let mut failed_days = vec![];
+
+    for day in all_days() {
+        // skip days that have not been scaffolded yet or have no inputs.
+        if !Path::new(&get_path_for_bin(day)).exists() || input_names(day).is_empty() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let day_padded = day.to_string();
+        let mut args = vec!["run", "--quiet", "--release", "--bin", &day_padded];
+
+        if cfg!(feature = "encrypted-inputs") {
+            args.extend(["--features", "encrypted-inputs"]);
+        }
+
+        args.extend(["--", "--all-inputs"]);
+
+        let status = Command::new("cargo")
+            .args(&args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .unwrap();
+
+        if !status.success() {
+            failed_days.push(day);
+        }
+
+        println!();
+    }
+
+    if failed_days.is_empty() {
+        println!("All recorded answers match.");
+    } else {
+        let days: Vec<String> = failed_days.iter().map(ToString::to_string).collect();
+        eprintln!("Answers do not match for day(s): {}", days.join(", "));
+        process::exit(1);
+    }
+}
@@ -0,0 +1,288 @@
+/// Reads hardware performance counters around bench iterations, for `cargo time --counters`.
+///
+/// Counting needs the `perf-counters` feature on Linux, where the counters are read with
+/// `perf_event_open(2)`. Elsewhere, [`Counters::open`] fails and nothing is counted.
+use std::fmt::{self, Display};
+
+pub use platform::Counters;
+
+/// Hardware events of a solution part, averaged over the bench iterations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HardwareCounters {
+    pub instructions: u64,
+    pub cycles: u64,
+    /// Last level cache misses.
+    pub cache_misses: u64,
+    pub branch_misses: u64,
+}
+
+impl HardwareCounters {
+    /// Instructions per cycle.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ipc(&self) -> f64 {
+        if self.cycles == 0 {
+            0.0
+        } else {
+            self.instructions as f64 / self.cycles as f64
+        }
+    }
+
+    /// Parses the format written by [`Display`], e.g.
+    /// `[9000 instrs · 3000 cycles · 3.00 IPC · 12 cache misses · 34 branch misses]`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let values: Vec<&str> = s
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .split(" · ")
+            .map(|value| value.split(' ').next())
+            .collect::<Option<_>>()?;
+
+        // the IPC at index 2 follows from instructions and cycles
+        match values[..] {
+            [instructions, cycles, _, cache_misses, branch_misses] => Some(Self {
+                instructions: instructions.parse().ok()?,
+                cycles: cycles.parse().ok()?,
+                cache_misses: cache_misses.parse().ok()?,
+                branch_misses: branch_misses.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Display for HardwareCounters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{} instrs · {} cycles · {:.2} IPC · {} cache misses · {} branch misses]",
+            self.instructions,
+            self.cycles,
+            self.ipc(),
+            self.cache_misses,
+            self.branch_misses
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(feature = "perf-counters", target_os = "linux"))]
+mod platform {
+    use std::io;
+    use std::mem::size_of;
+
+    use super::HardwareCounters;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+    const PERF_FORMAT_GROUP: u64 = 1 << 3;
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+    const PERF_IOC_FLAG_GROUP: libc::c_int = 1;
+    const PERF_EVENT_IOC_ENABLE: libc::Ioctl = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: libc::Ioctl = 0x2401;
+    const PERF_EVENT_IOC_RESET: libc::Ioctl = 0x2403;
+
+    /// Bits of `perf_event_attr.flags`.
+    const DISABLED: u64 = 1 << 0;
+    const EXCLUDE_KERNEL: u64 = 1 << 5;
+    const EXCLUDE_HV: u64 = 1 << 6;
+
+    /// The events of the group, the first one leading it. Their values are read in this order.
+    const EVENTS: [u64; 4] = [
+        PERF_COUNT_HW_CPU_CYCLES,
+        PERF_COUNT_HW_INSTRUCTIONS,
+        PERF_COUNT_HW_CACHE_MISSES,
+        PERF_COUNT_HW_BRANCH_MISSES,
+    ];
+
+    /// The first version of `struct perf_event_attr`, see `linux/perf_event.h`.
+    /// The kernel accepts it and zeroes the fields added later.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    /// A group of counters for the user space events of the current thread, which are
+    /// started and stopped together.
+    pub struct Counters {
+        fds: Vec<libc::c_int>,
+        totals: [u64; EVENTS.len()],
+        iterations: u64,
+    }
+
+    impl Counters {
+        pub fn open() -> Result<Self, String> {
+            let mut counters = Self {
+                fds: vec![],
+                totals: [0; EVENTS.len()],
+                iterations: 0,
+            };
+
+            for event in EVENTS {
+                let leader = counters.fds.first().copied();
+                let attr = PerfEventAttr {
+                    kind: PERF_TYPE_HARDWARE,
+                    size: u32::try_from(size_of::<PerfEventAttr>()).unwrap(),
+                    config: event,
+                    read_format: PERF_FORMAT_GROUP,
+                    // members count whenever the leader does
+                    flags: EXCLUDE_KERNEL
+                        | EXCLUDE_HV
+                        | if leader.is_none() { DISABLED } else { 0 },
+                    ..PerfEventAttr::default()
+                };
+
+                let fd = unsafe {
+                    libc::syscall(
+                        libc::SYS_perf_event_open,
+                        &raw const attr,
+                        0 as libc::pid_t,
+                        -1 as libc::c_int,
+                        leader.unwrap_or(-1),
+                        PERF_FLAG_FD_CLOEXEC,
+                    )
+                };
+                if fd < 0 {
+                    let error = io::Error::last_os_error();
+                    let hint = match error.raw_os_error() {
+                        Some(libc::EACCES | libc::EPERM) => {
+                            "Counting may need a lower `/proc/sys/kernel/perf_event_paranoid`."
+                        }
+                        Some(libc::ENOENT | libc::EOPNOTSUPP) => {
+                            "The CPU, or the virtual machine, does not expose this event."
+                        }
+                        _ => "",
+                    };
+                    return Err(format!("{error}. {hint}"));
+                }
+                counters.fds.push(libc::c_int::try_from(fd).unwrap());
+            }
+
+            Ok(counters)
+        }
+
+        /// Resets and starts the counters.
+        pub fn start(&self) {
+            self.ioctl(PERF_EVENT_IOC_RESET);
+            self.ioctl(PERF_EVENT_IOC_ENABLE);
+        }
+
+        /// Stops the counters and adds their values to the totals of one more iteration.
+        pub fn stop(&mut self) {
+            self.ioctl(PERF_EVENT_IOC_DISABLE);
+
+            // the number of events, then their values
+            let mut values = [0_u64; 1 + EVENTS.len()];
+            let read = unsafe {
+                libc::read(
+                    self.fds[0],
+                    values.as_mut_ptr().cast(),
+                    size_of::<[u64; 1 + EVENTS.len()]>(),
+                )
+            };
+            if read > 0 {
+                for (total, value) in self.totals.iter_mut().zip(&values[1..]) {
+                    *total += value;
+                }
+                self.iterations += 1;
+            }
+        }
+
+        /// The counted events per iteration.
+        pub fn per_iteration(&self) -> HardwareCounters {
+            let [cycles, instructions, cache_misses, branch_misses] =
+                self.totals.map(|total| total / self.iterations.max(1));
+            HardwareCounters {
+                instructions,
+                cycles,
+                cache_misses,
+                branch_misses,
+            }
+        }
+
+        fn ioctl(&self, request: libc::Ioctl) {
+            unsafe {
+                libc::ioctl(self.fds[0], request, PERF_IOC_FLAG_GROUP);
+            }
+        }
+    }
+
+    impl Drop for Counters {
+        fn drop(&mut self) {
+            for fd in &self.fds {
+                unsafe {
+                    libc::close(*fd);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(all(feature = "perf-counters", target_os = "linux")))]
+mod platform {
+    use super::HardwareCounters;
+
+    /// Stands in for the counters where they cannot be read.
+    pub struct Counters;
+
+    impl Counters {
+        pub fn open() -> Result<Self, String> {
+            Err("counting needs the `perf-counters` feature on Linux.".into())
+        }
+
+        pub fn start(&self) {}
+
+        pub fn stop(&mut self) {}
+
+        pub fn per_iteration(&self) -> HardwareCounters {
+            HardwareCounters::default()
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::HardwareCounters;
+
+    #[test]
+    fn round_trips_counters() {
+        let counters = HardwareCounters {
+            instructions: 9000,
+            cycles: 4000,
+            cache_misses: 12,
+            branch_misses: 34,
+        };
+        assert_eq!(
+            counters.to_string(),
+            "[9000 instrs · 4000 cycles · 2.25 IPC · 12 cache misses · 34 branch misses]"
+        );
+        assert_eq!(
+            HardwareCounters::parse(&counters.to_string()),
+            Some(counters)
+        );
+        assert_eq!(
+            HardwareCounters::parse("[2048 B peak · 4096 B total · 3 allocs]"),
+            None
+        );
+    }
+
+    #[test]
+    fn computes_ipc() {
+        assert_eq!(HardwareCounters::default().ipc(), 0.0);
+    }
+}
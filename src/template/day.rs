@@ -57,6 +57,13 @@ impl Display for Day {
     }
 }
 
+/// The first day, so types holding a [`Day`] can derive [`Default`].
+impl Default for Day {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
 impl PartialEq<u8> for Day {
     fn eq(&self, other: &u8) -> bool {
         self.0.eq(other)
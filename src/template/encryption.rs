@@ -0,0 +1,236 @@
+/// Symmetric encryption of puzzle inputs, so they can be committed to `data/inputs.enc/` without being published.
+///
+/// The `AOC_INPUT_KEY` environment variable holds a random 256-bit key in base64, e.g. from
+/// `openssl rand -base64 32`. Inputs are encrypted with AES-256-GCM-SIV. Its nonce is an HMAC
+/// of the plaintext under a key derived from the input key, so encryption is deterministic for
+/// unchanged inputs, but without the key a file reveals nothing about its input and cannot be
+/// linked to the encryption of the same input under another key.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aes_gcm_siv::{
+    Aes256GcmSiv, Nonce,
+    aead::{Aead, KeyInit},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const KEY_ENV_VAR: &str = "AOC_INPUT_KEY";
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    MissingKey,
+    InvalidKey,
+    Malformed,
+    Cipher,
+    IO(io::Error),
+}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::MissingKey => {
+                write!(f, "environment variable {KEY_ENV_VAR} is not set.")
+            }
+            EncryptionError::InvalidKey => {
+                write!(
+                    f,
+                    "environment variable {KEY_ENV_VAR} must hold {KEY_LEN} random bytes in base64, e.g. from `openssl rand -base64 {KEY_LEN}`."
+                )
+            }
+            EncryptionError::Malformed => write!(f, "encrypted file is too short."),
+            EncryptionError::Cipher => {
+                write!(
+                    f,
+                    "decryption failed, the key does not match or the file is corrupted."
+                )
+            }
+            EncryptionError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for EncryptionError {
+    fn from(e: io::Error) -> Self {
+        EncryptionError::IO(e)
+    }
+}
+
+/// The cipher and the key the nonces are derived with, both derived from the input key so
+/// neither is used for two purposes.
+struct Cipher {
+    aead: Aes256GcmSiv,
+    nonce_key: [u8; KEY_LEN],
+}
+
+impl Cipher {
+    fn new(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            aead: Aes256GcmSiv::new(&hmac_sha256(key, b"encryption").into()),
+            nonce_key: hmac_sha256(key, b"nonce"),
+        }
+    }
+}
+
+fn cipher() -> Result<Cipher, EncryptionError> {
+    let key = env::var(KEY_ENV_VAR).map_err(|_| EncryptionError::MissingKey)?;
+    Ok(Cipher::new(&parse_key(&key)?))
+}
+
+/// Decodes a key from strict base64, with padding.
+fn parse_key(text: &str) -> Result<[u8; KEY_LEN], EncryptionError> {
+    STANDARD
+        .decode(text.trim())
+        .ok()
+        .and_then(|key| key.try_into().ok())
+        .ok_or(EncryptionError::InvalidKey)
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+fn encrypt_with(cipher: &Cipher, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    let digest = hmac_sha256(&cipher.nonce_key, plaintext);
+    let nonce = Nonce::from_slice(&digest[..NONCE_LEN]);
+    let ciphertext = cipher
+        .aead
+        .encrypt(nonce, plaintext)
+        .map_err(|_| EncryptionError::Cipher)?;
+    Ok([nonce.as_slice(), &ciphertext].concat())
+}
+
+fn decrypt_with(cipher: &Cipher, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    if data.len() < NONCE_LEN {
+        return Err(EncryptionError::Malformed);
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    cipher
+        .aead
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| EncryptionError::Cipher)
+}
+
+/// Whether a key is set to decrypt inputs with.
+pub fn has_key() -> bool {
+    env::var_os(KEY_ENV_VAR).is_some()
+}
+
+/// Encrypts some bytes with the key from the environment.
+pub fn encrypt(plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    encrypt_with(&cipher()?, plaintext)
+}
+
+/// Decrypts bytes produced by [`encrypt`] with the key from the environment.
+pub fn decrypt(data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    decrypt_with(&cipher()?, data)
+}
+
+/// Maps a path below `data/inputs` to its encrypted counterpart below `data/inputs.enc`.
+pub fn encrypted_path(path: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix("data/inputs").ok()?;
+    Some(Path::new("data/inputs.enc").join(relative))
+}
+
+/// Reads and decrypts the encrypted counterpart of an input path.
+pub fn read_decrypted(path: &Path) -> Result<Vec<u8>, EncryptionError> {
+    let encrypted_path = encrypted_path(path).ok_or(EncryptionError::Malformed)?;
+    decrypt(&fs::read(encrypted_path)?)
+}
+
+/// Encrypts an input file to its counterpart below `data/inputs.enc`.
+/// Returns the path of the encrypted file.
+pub fn encrypt_file(path: &Path) -> Result<PathBuf, EncryptionError> {
+    let target = encrypted_path(path).ok_or(EncryptionError::Malformed)?;
+    let encrypted = encrypt(&fs::read(path)?)?;
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&target, encrypted)?;
+
+    Ok(target)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{Cipher, decrypt_with, encrypt_with, encrypted_path, hmac_sha256, parse_key};
+
+    #[test]
+    fn roundtrips_inputs() {
+        let cipher = Cipher::new(&[7; 32]);
+        let encrypted = encrypt_with(&cipher, b"L68\nL30\n").unwrap();
+        assert_ne!(&encrypted[12..], b"L68\nL30\n");
+        assert_eq!(decrypt_with(&cipher, &encrypted).unwrap(), b"L68\nL30\n");
+    }
+
+    #[test]
+    fn encrypts_deterministically() {
+        let cipher = Cipher::new(&[7; 32]);
+        assert_eq!(
+            encrypt_with(&cipher, b"L68\n").unwrap(),
+            encrypt_with(&cipher, b"L68\n").unwrap()
+        );
+    }
+
+    #[test]
+    fn derives_nonces_with_the_key() {
+        let first = encrypt_with(&Cipher::new(&[7; 32]), b"L68\n").unwrap();
+        let second = encrypt_with(&Cipher::new(&[8; 32]), b"L68\n").unwrap();
+        assert_ne!(first[..12], second[..12]);
+    }
+
+    #[test]
+    fn rejects_wrong_key() {
+        let encrypted = encrypt_with(&Cipher::new(&[7; 32]), b"L68\n").unwrap();
+        assert!(decrypt_with(&Cipher::new(&[8; 32]), &encrypted).is_err());
+        assert!(decrypt_with(&Cipher::new(&[7; 32]), &encrypted[..4]).is_err());
+    }
+
+    #[test]
+    fn computes_hmac() {
+        // test case 2 of RFC 4231
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        let hex: String = mac.iter().map(|byte| format!("{byte:02x}")).collect();
+        assert_eq!(
+            hex,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn parses_keys() {
+        let key = "BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=";
+        assert_eq!(parse_key(key).unwrap(), [7; 32]);
+        assert_eq!(parse_key(&format!("{key}\n")).unwrap(), [7; 32]);
+        // too short, unpadded, with trailing bits, and not base64
+        assert!(parse_key("aHVudGVyMg==").is_err());
+        assert!(parse_key(key.trim_end_matches('=')).is_err());
+        assert!(parse_key("BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwd=").is_err());
+        assert!(parse_key("hunter 2").is_err());
+    }
+
+    #[test]
+    fn maps_encrypted_paths() {
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/01/alice.txt")).unwrap(),
+            Path::new("data/inputs.enc/01/alice.txt")
+        );
+        assert_eq!(encrypted_path(Path::new("data/examples/01.txt")), None);
+    }
+}
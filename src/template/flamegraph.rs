@@ -0,0 +1,170 @@
+/// Renders sampled call stacks as a flame graph SVG, for `cargo solve <day> --profile`.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+const WIDTH: f64 = 1200.0;
+const FRAME_HEIGHT: usize = 16;
+const HEADER_HEIGHT: usize = 32;
+const FONT_SIZE: usize = 11;
+/// Approximate width of a character of the monospace font.
+const CHAR_WIDTH: f64 = FONT_SIZE as f64 * 0.6;
+/// Frames narrower than this many pixels are left out.
+const MIN_FRAME_WIDTH: f64 = 0.1;
+
+/// Call stacks, outermost frame first, with the number of samples each was seen in.
+pub type Stacks = HashMap<Vec<String>, usize>;
+
+#[derive(Default)]
+struct Node {
+    samples: usize,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn depth(&self) -> usize {
+        self.children
+            .values()
+            .map(|child| child.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Renders a flame graph: the outermost frames at the bottom, callees stacked on their callers,
+/// and each frame as wide as the share of samples it was on the stack for. Callees are ordered
+/// by name, so neighbouring frames are not related in time.
+pub fn render(title: &str, stacks: &Stacks) -> String {
+    let mut root = Node::default();
+    for (stack, samples) in stacks {
+        root.samples += samples;
+        let mut node = &mut root;
+        for frame in stack {
+            node = node.children.entry(frame.clone()).or_default();
+            node.samples += samples;
+        }
+    }
+
+    let height = HEADER_HEIGHT + (root.depth() + 1) * FRAME_HEIGHT;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" \
+         viewBox=\"0 0 {WIDTH} {height}\" font-family=\"monospace\" font-size=\"{FONT_SIZE}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#f8f8f8\"/>\n\
+         <text x=\"{}\" y=\"20\" text-anchor=\"middle\" font-size=\"14\">{}</text>\n",
+        WIDTH / 2.0,
+        escape(title)
+    );
+
+    if root.samples > 0 {
+        let mut frames = vec![("all".to_string(), &root, 0.0, 0)];
+        while let Some((name, node, x, depth)) = frames.pop() {
+            #[allow(clippy::cast_precision_loss)]
+            let width = WIDTH * node.samples as f64 / root.samples as f64;
+            if width < MIN_FRAME_WIDTH {
+                continue;
+            }
+            let y = height - (depth + 1) * FRAME_HEIGHT;
+            write_frame(&mut svg, &name, node.samples, root.samples, x, y, width);
+
+            let mut child_x = x;
+            for (name, child) in &node.children {
+                frames.push((name.clone(), child, child_x, depth + 1));
+                #[allow(clippy::cast_precision_loss)]
+                let child_width = WIDTH * child.samples as f64 / root.samples as f64;
+                child_x += child_width;
+            }
+        }
+    }
+
+    svg += "</svg>\n";
+    svg
+}
+
+fn write_frame(
+    svg: &mut String,
+    name: &str,
+    samples: usize,
+    total: usize,
+    x: f64,
+    y: usize,
+    width: f64,
+) {
+    #[allow(clippy::cast_precision_loss)]
+    let share = 100.0 * samples as f64 / total as f64;
+    let (r, g, b) = color(name);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let fitting_chars = ((width - 6.0) / CHAR_WIDTH).max(0.0) as usize;
+    let label = match name.chars().count() {
+        _ if fitting_chars < 3 => String::new(),
+        len if len <= fitting_chars => name.to_string(),
+        _ => name.chars().take(fitting_chars - 2).collect::<String>() + "..",
+    };
+
+    let _ = writeln!(
+        svg,
+        "<g><title>{} ({samples} samples, {share:.2}%)</title>\
+         <rect x=\"{x:.2}\" y=\"{y}\" width=\"{width:.2}\" height=\"{}\" fill=\"rgb({r},{g},{b})\" rx=\"2\"/>\
+         <text x=\"{:.2}\" y=\"{}\">{}</text></g>",
+        escape(name),
+        FRAME_HEIGHT - 1,
+        x + 3.0,
+        y + FONT_SIZE,
+        escape(&label)
+    );
+}
+
+/// A warm colour that stays the same for a function across runs.
+#[allow(clippy::cast_possible_truncation)]
+fn color(name: &str) -> (u8, u8, u8) {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    let hash = hasher.finish();
+    (
+        205 + (hash % 50) as u8,
+        (hash >> 8) as u8 % 230,
+        (hash >> 16) as u8 % 55,
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Stacks, render};
+
+    fn stack(frames: &[&str]) -> Vec<String> {
+        frames.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn renders_frames() {
+        let stacks = Stacks::from([
+            (stack(&["_08::part_one", "_08::parse_input"]), 1),
+            (stack(&["_08::part_one", "core::slice::sort<T>"]), 3),
+        ]);
+        let svg = render("Day 08", &stacks);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">Day 08</text>"));
+        assert!(svg.contains("<title>all (4 samples, 100.00%)</title>"));
+        assert!(svg.contains("<title>_08::part_one (4 samples, 100.00%)</title>"));
+        assert!(svg.contains("<title>core::slice::sort&lt;T&gt; (3 samples, 75.00%)</title>"));
+        assert!(svg.contains("width=\"900.00\""));
+        assert!(svg.contains("width=\"300.00\""));
+    }
+
+    #[test]
+    fn renders_empty_profile() {
+        let svg = render("nothing", &Stacks::new());
+        assert!(!svg.contains("<g>"));
+    }
+}
@@ -0,0 +1,314 @@
+/// Checksums of downloaded inputs and checks that detect truncated or modified input files.
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
+
+use sha2::{Digest, Sha256};
+use tinyjson::JsonValue;
+
+use crate::template::{DEFAULT_INPUT_NAME, Day, answers::Answers, read_bytes};
+
+static MANIFEST_FILE_PATH: &str = "./data/inputs.json";
+
+/// Represents the checksum of a single input file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputChecksum {
+    pub day: Day,
+    pub input: String,
+    pub sha256: String,
+    pub bytes: usize,
+}
+
+impl InputChecksum {
+    /// Computes the checksum of an input's contents.
+    pub fn new(day: Day, input: &str, contents: &[u8]) -> Self {
+        Self {
+            day,
+            input: input.to_string(),
+            sha256: sha256_hex(contents),
+            bytes: contents.len(),
+        }
+    }
+}
+
+/// Represents the checksums of all downloaded inputs.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct InputManifest {
+    pub data: Vec<InputChecksum>,
+}
+
+impl InputManifest {
+    /// Dehydrate the manifest to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(MANIFEST_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the manifest from a JSON file. If not present, returns an empty manifest.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(MANIFEST_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(InputManifest::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Look up the checksum of a day's named input.
+    pub fn get(&self, day: Day, input: &str) -> Option<&InputChecksum> {
+        self.data.iter().find(|c| c.day == day && c.input == input)
+    }
+
+    /// Add a checksum, replacing a previous one for the same input.
+    pub fn set(&mut self, checksum: InputChecksum) {
+        self.data
+            .retain(|c| c.day != checksum.day || c.input != checksum.input);
+        self.data.push(checksum);
+        self.data
+            .sort_by(|a, b| a.day.cmp(&b.day).then_with(|| a.input.cmp(&b.input)));
+    }
+}
+
+/// Hex-encoded SHA-256 digest of some bytes.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Path of a day's named input, relative to the working directory.
+pub fn input_path(day: Day, input: &str) -> PathBuf {
+    if input == DEFAULT_INPUT_NAME {
+        PathBuf::from(format!("data/inputs/{day}.txt"))
+    } else {
+        PathBuf::from(format!("data/inputs/{day}/{input}.txt"))
+    }
+}
+
+/// Reads an input file and computes its checksum.
+pub fn read_checksum(day: Day, input: &str) -> Result<InputChecksum, Error> {
+    let contents = read_bytes(&input_path(day, input))?;
+    Ok(InputChecksum::new(day, input, &contents))
+}
+
+/// Records the checksum of a freshly downloaded input in the manifest.
+pub fn record_download(day: Day) -> Result<(), Error> {
+    let checksum = read_checksum(day, DEFAULT_INPUT_NAME)?;
+    let mut manifest = InputManifest::read_from_file();
+    manifest.set(checksum);
+    manifest.store_file()
+}
+
+/// Returns a warning for every integrity problem of an input's raw contents.
+pub fn check(
+    contents: &[u8],
+    checksum: &InputChecksum,
+    manifest: &InputManifest,
+    answers: &Answers,
+) -> Vec<String> {
+    let mut warnings = vec![];
+
+    if contents.is_empty() {
+        warnings.push("is empty.".to_string());
+        return warnings;
+    }
+
+    if !contents.ends_with(b"\n") {
+        warnings.push("does not end with a line break and may have been truncated.".to_string());
+    }
+
+    if let Some(downloaded) = manifest.get(checksum.day, &checksum.input)
+        && downloaded.sha256 != checksum.sha256
+    {
+        warnings.push(format!(
+            "differs from the downloaded input ({} bytes now, {} bytes when downloaded).",
+            checksum.bytes, downloaded.bytes
+        ));
+    }
+
+    if let Some(recorded) = answers.input_sha256(checksum.day, &checksum.input)
+        && recorded != checksum.sha256
+    {
+        warnings.push("has changed since its answers were recorded.".to_string());
+    }
+
+    warnings
+}
+
+/// Prints a warning to stderr for every integrity problem of an input's raw contents.
+pub fn warn(day: Day, input: &str, contents: &[u8]) {
+    let checksum = InputChecksum::new(day, input, contents);
+    let warnings = check(
+        contents,
+        &checksum,
+        &InputManifest::read_from_file(),
+        &Answers::read_from_file(),
+    );
+
+    for warning in warnings {
+        eprintln!("Warning: {} {warning}", input_path(day, input).display());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<InputManifest> for JsonValue {
+    fn from(value: InputManifest) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for InputManifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(InputManifest {
+            data: json_data
+                .iter()
+                .map(InputChecksum::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&InputChecksum> for JsonValue {
+    fn from(value: &InputChecksum) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("input".into(), JsonValue::String(value.input.clone()));
+        map.insert("sha256".into(), JsonValue::String(value.sha256.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for InputChecksum {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected checksum to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected checksum.day to be a Day struct.")?;
+
+        let input = json
+            .get("input")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected checksum.input to be a string.")?;
+
+        let sha256 = json
+            .get("sha256")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected checksum.sha256 to be a string.")?;
+
+        let bytes = json
+            .get("bytes")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected checksum.bytes to be a number.")?;
+
+        Ok(InputChecksum {
+            day,
+            input: input.clone(),
+            sha256: sha256.clone(),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            bytes: bytes as usize,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputChecksum, InputManifest, check, sha256_hex};
+    use crate::{
+        day,
+        template::{DEFAULT_INPUT_NAME, answers::Answers},
+    };
+
+    const INPUT: &[u8] = b"L68\nL30\n";
+
+    fn get_mock_manifest() -> InputManifest {
+        InputManifest {
+            data: vec![InputChecksum::new(day!(1), DEFAULT_INPUT_NAME, INPUT)],
+        }
+    }
+
+    fn check_contents(contents: &[u8], answers: &Answers) -> Vec<String> {
+        let checksum = InputChecksum::new(day!(1), DEFAULT_INPUT_NAME, contents);
+        check(contents, &checksum, &get_mock_manifest(), answers)
+    }
+
+    #[test]
+    fn hashes_sha256() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn accepts_unchanged_input() {
+        assert!(check_contents(INPUT, &Answers::default()).is_empty());
+    }
+
+    #[test]
+    fn warns_for_empty_input() {
+        assert_eq!(check_contents(b"", &Answers::default()), vec!["is empty."]);
+    }
+
+    #[test]
+    fn warns_for_truncated_input() {
+        let warnings = check_contents(b"L68\nL3", &Answers::default());
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("line break"));
+        assert!(warnings[1].contains("downloaded input"));
+    }
+
+    #[test]
+    fn warns_for_input_changed_since_answers_were_recorded() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), DEFAULT_INPUT_NAME, 1, "3".into());
+        answers.set_input_sha256(day!(1), DEFAULT_INPUT_NAME, sha256_hex(b"L1\n"));
+        let warnings = check_contents(INPUT, &answers);
+        assert_eq!(
+            warnings,
+            vec!["has changed since its answers were recorded."]
+        );
+    }
+
+    #[test]
+    fn roundtrips_manifest() {
+        let json = tinyjson::JsonValue::from(get_mock_manifest())
+            .stringify()
+            .unwrap();
+        let manifest = InputManifest::try_from(json).unwrap();
+        assert_eq!(manifest.data, get_mock_manifest().data);
+    }
+}
@@ -0,0 +1,203 @@
+/// The machine benchmarks run on: its CPU, the settings that make timings vary and the
+/// compiler, for `cargo time`.
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+#[cfg(not(target_os = "linux"))]
+use std::thread;
+
+const CPU_DIR: &str = "/sys/devices/system/cpu";
+
+/// The model name of the CPU, e.g. `AMD Ryzen 7 5800X 8-Core Processor`.
+pub fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        return parse_cpu_model(&cpuinfo);
+    }
+
+    // macOS
+    let output = Command::new("sysctl")
+        .args(["-n", "machdep.cpu.brand_string"])
+        .output()
+        .ok()?;
+    let model = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !model.is_empty()).then_some(model)
+}
+
+/// The version of the compiler that builds the solutions, e.g. `rustc 1.91.0 (f8297e351 2025-10-28)`.
+pub fn rustc_version() -> Option<String> {
+    let output = Command::new("rustc").arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !version.is_empty()).then_some(version)
+}
+
+/// Warnings about CPU frequency settings that make timings vary between runs.
+/// Empty if the settings cannot be read, e.g. on other systems than Linux.
+pub fn frequency_warnings() -> Vec<String> {
+    let mut governors: Vec<String> = fs::read_dir(CPU_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            fs::read_to_string(entry.path().join("cpufreq").join("scaling_governor")).ok()
+        })
+        .map(|governor| governor.trim().to_string())
+        .collect();
+    governors.sort();
+    governors.dedup();
+
+    let read = |path: &str| fs::read_to_string(Path::new(CPU_DIR).join(path)).ok();
+
+    [
+        governor_warning(&governors),
+        turbo_warning(
+            read("intel_pstate/no_turbo").as_deref(),
+            read("cpufreq/boost").as_deref(),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// The core `--pin` pins to without `--core`: the last one the process may run on, as the
+/// first tends to handle more interrupts. Under a cpuset or `taskset`, that is the highest core
+/// of the affinity mask rather than the number of cores.
+#[cfg(target_os = "linux")]
+pub fn default_core() -> usize {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        if libc::sched_getaffinity(0, size_of::<libc::cpu_set_t>(), &raw mut set) != 0 {
+            return 0;
+        }
+        let cores = usize::try_from(libc::CPU_SETSIZE).unwrap_or(0);
+        (0..cores)
+            .rev()
+            .find(|core| libc::CPU_ISSET(*core, &set))
+            .unwrap_or(0)
+    }
+}
+
+/// The core `--pin` pins to without `--core`: the last one, as the first tends to handle
+/// more interrupts.
+#[cfg(not(target_os = "linux"))]
+pub fn default_core() -> usize {
+    thread::available_parallelism().map_or(0, |cores| cores.get() - 1)
+}
+
+/// Pins the current thread to a core, so the scheduler does not move it between runs.
+#[cfg(target_os = "linux")]
+pub fn pin_thread(core: usize) -> Result<(), String> {
+    // `CPU_SET` indexes a fixed-size set and panics beyond it
+    let cores = usize::try_from(libc::CPU_SETSIZE).unwrap_or(0);
+    if core >= cores {
+        return Err(format!("cores are numbered below {cores}."));
+    }
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        if libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), &raw const set) != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Pins the current thread to a core, so the scheduler does not move it between runs.
+#[cfg(not(target_os = "linux"))]
+pub fn pin_thread(_core: usize) -> Result<(), String> {
+    Err("pinning is only supported on Linux.".into())
+}
+
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim() == "model name")
+        .map(|(_, model)| model.trim().to_string())
+}
+
+fn governor_warning(governors: &[String]) -> Option<String> {
+    let others: Vec<&str> = governors
+        .iter()
+        .map(String::as_str)
+        .filter(|governor| *governor != "performance")
+        .collect();
+
+    (!others.is_empty()).then(|| {
+        format!(
+            "the CPU frequency governor is `{}` instead of `performance`. Switch with `sudo cpupower frequency-set -g performance`.",
+            others.join("`, `")
+        )
+    })
+}
+
+/// `no_turbo` is set by the `intel_pstate` driver, `boost` by other drivers.
+fn turbo_warning(no_turbo: Option<&str>, boost: Option<&str>) -> Option<String> {
+    let (path, off) = match (no_turbo.map(str::trim), boost.map(str::trim)) {
+        (Some("0"), _) => ("intel_pstate/no_turbo", "1"),
+        (_, Some("1")) => ("cpufreq/boost", "0"),
+        _ => return None,
+    };
+
+    Some(format!(
+        "turbo boost is on, so the clock speed depends on temperature. Turn it off with `echo {off} | sudo tee {CPU_DIR}/{path}`."
+    ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{default_core, governor_warning, parse_cpu_model, pin_thread, turbo_warning};
+
+    #[test]
+    fn parses_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD EPYC 7B13\nflags\t\t: fpu\n";
+        assert_eq!(parse_cpu_model(cpuinfo), Some("AMD EPYC 7B13".into()));
+        assert_eq!(parse_cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn warns_about_governors() {
+        assert_eq!(governor_warning(&["performance".into()]), None);
+        assert_eq!(governor_warning(&[]), None);
+        assert!(
+            governor_warning(&["performance".into(), "powersave".into()])
+                .unwrap()
+                .starts_with("the CPU frequency governor is `powersave` instead")
+        );
+    }
+
+    #[test]
+    fn warns_about_turbo() {
+        assert_eq!(turbo_warning(None, None), None);
+        assert_eq!(turbo_warning(Some("1\n"), None), None);
+        assert_eq!(turbo_warning(None, Some("0\n")), None);
+        assert!(
+            turbo_warning(Some("0\n"), None)
+                .unwrap()
+                .contains("echo 1 | sudo tee")
+        );
+        assert!(
+            turbo_warning(None, Some("1\n"))
+                .unwrap()
+                .contains("cpufreq/boost")
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn rejects_cores_beyond_the_cpu_set() {
+        assert_eq!(
+            pin_thread(5000),
+            Err("cores are numbered below 1024.".into())
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn pins_to_default_core() {
+        // fails for a core outside the affinity mask, e.g. under `taskset -c 0`
+        assert_eq!(pin_thread(default_core()), Ok(()));
+    }
+}
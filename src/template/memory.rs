@@ -0,0 +1,160 @@
+/// Counts heap allocations of a solution part, for `cargo time --memory`.
+///
+/// The `solution!` macro installs [`CountingAlloc`] as the global allocator of every solution
+/// binary, unless the `dhat-heap` feature installs DHAT's. It only counts inside [`measure`].
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+/// Bytes allocated since tracking started, minus the bytes freed. Can go below zero if memory
+/// allocated before is freed.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting allocations while [`measure`] runs.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    #[allow(clippy::cast_possible_wrap)]
+    fn record(grown: usize, shrunk: usize) {
+        if !TRACKING.load(Ordering::Relaxed) {
+            return;
+        }
+        if grown > 0 {
+            TOTAL.fetch_add(grown, Ordering::Relaxed);
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        let delta = grown as isize - shrunk as isize;
+        let current = CURRENT.fetch_add(delta, Ordering::Relaxed) + delta;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size(), 0);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size(), 0);
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        Self::record(0, layout.size());
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record(new_size, layout.size());
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Heap usage of one run of a solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The most memory held at once, on top of what was held before the run.
+    pub peak_bytes: usize,
+    /// The sum of all allocation sizes. A reallocation counts with its new size.
+    pub total_bytes: usize,
+    /// The number of allocations and reallocations.
+    pub allocations: usize,
+}
+
+impl MemoryUsage {
+    /// Parses the format written by [`Display`], e.g. `[2048 B peak · 4096 B total · 3 allocs]`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut values = s
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .split(" · ")
+            .map(|value| value.split(' ').next()?.parse().ok());
+
+        Some(Self {
+            peak_bytes: values.next()??,
+            total_bytes: values.next()??,
+            allocations: values.next()??,
+        })
+    }
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{} B peak · {} B total · {} allocs]",
+            self.peak_bytes, self.total_bytes, self.allocations
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Runs a function and counts the heap memory it uses.
+///
+/// Counts nothing if [`CountingAlloc`] is not the global allocator.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, MemoryUsage) {
+    CURRENT.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    TOTAL.store(0, Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+
+    TRACKING.store(true, Ordering::Relaxed);
+    let result = func();
+    TRACKING.store(false, Ordering::Relaxed);
+
+    let usage = MemoryUsage {
+        peak_bytes: PEAK.load(Ordering::Relaxed).max(0).unsigned_abs(),
+        total_bytes: TOTAL.load(Ordering::Relaxed),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+    };
+    (result, usage)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MemoryUsage, format_bytes};
+
+    #[test]
+    fn round_trips_usage() {
+        let usage = MemoryUsage {
+            peak_bytes: 2048,
+            total_bytes: 4096,
+            allocations: 3,
+        };
+        assert_eq!(usage.to_string(), "[2048 B peak · 4096 B total · 3 allocs]");
+        assert_eq!(MemoryUsage::parse(&usage.to_string()), Some(usage));
+        assert_eq!(MemoryUsage::parse("[2048 B peak]"), None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
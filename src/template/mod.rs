@@ -1,68 +1,395 @@
-use std::{env, fs};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod memory;
 pub mod runner;
 
 pub use day::*;
+pub use normalize::Normalization;
 
+use normalize::warn_unexpected_chars;
+
+mod answers;
+mod counters;
 mod day;
+#[cfg(feature = "encrypted-inputs")]
+mod encryption;
+#[cfg(all(feature = "cpu-profile", unix))]
+mod flamegraph;
+mod integrity;
+mod machine;
+mod normalize;
+mod profiles;
 mod readme_benchmarks;
 mod run_multi;
+#[cfg(all(feature = "cpu-profile", unix))]
+mod sampler;
+mod scaling;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Name under which the day's primary input `data/inputs/NN.txt` is listed.
+pub const DEFAULT_INPUT_NAME: &str = "default";
+
+/// Size of generated inputs if `cargo gen` is called without `--size`.
+pub const DEFAULT_GENERATED_SIZE: usize = 100;
+
+/// Seed of generated inputs if `cargo gen` is called without `--seed`.
+pub const DEFAULT_GENERATED_SEED: u64 = 1;
+
+/// Helper function that reads a text file to a string, applying [`Normalization::DEFAULT`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_file_normalized(folder, day, Normalization::DEFAULT)
+}
+
+/// Helper function that reads a text file to a string, applying the given normalisation policy.
+#[must_use]
+pub fn read_file_normalized(folder: &str, day: Day, normalization: Normalization) -> String {
+    read_data_file(&Path::new(folder).join(format!("{day}.txt")), normalization)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_file_part_normalized(folder, day, part, Normalization::DEFAULT)
+}
+
+/// Like [`read_file_part`], applying the given normalisation policy.
+#[must_use]
+pub fn read_file_part_normalized(
+    folder: &str,
+    day: Day,
+    part: u8,
+    normalization: Normalization,
+) -> String {
+    read_data_file(
+        &Path::new(folder).join(format!("{day}-{part}.txt")),
+        normalization,
+    )
+}
+
+/// Helper function that reads a named text file to a string from a per-day subfolder. E.g. like `01/alice.txt`.
+#[must_use]
+pub fn read_file_named(folder: &str, day: Day, name: &str) -> String {
+    read_data_file(
+        &Path::new(folder)
+            .join(day.to_string())
+            .join(format!("{name}.txt")),
+        Normalization::DEFAULT,
+    )
+}
+
+/// Reads a file relative to `./data` and normalises it.
+fn read_data_file(path: &Path, normalization: Normalization) -> String {
+    let path = Path::new("data").join(path);
+    let raw = read_bytes(&path).expect("could not open input file");
+    let raw = String::from_utf8(raw).expect("input file is not valid UTF-8");
+    normalize_contents(&raw, normalization, &path.display().to_string())
+}
+
+/// Reads a file relative to the working directory.
+///
+/// With the `encrypted-inputs` feature, inputs missing from `data/inputs` are transparently
+/// decrypted from their counterpart in `data/inputs.enc`.
+pub(crate) fn read_bytes(path: &Path) -> io::Result<Vec<u8>> {
+    let filepath = env::current_dir()?.join(path);
+
+    #[cfg(feature = "encrypted-inputs")]
+    if !filepath.exists()
+        && encryption::encrypted_path(path).is_some_and(|encrypted| encrypted.exists())
+    {
+        return encryption::read_decrypted(path).map_err(|e| io::Error::other(e.to_string()));
+    }
+
+    fs::read(filepath)
+}
+
+/// Applies a normalisation policy and warns about unexpected characters.
+fn normalize_contents(raw: &str, normalization: Normalization, origin: &str) -> String {
+    let normalized = normalization.apply(raw).into_owned();
+    warn_unexpected_chars(&normalized, origin);
+    normalized
+}
+
+/// Lists the names of all inputs available for a day, sorted.
+///
+/// The primary input `data/inputs/NN.txt` is listed as [`DEFAULT_INPUT_NAME`],
+/// every `data/inputs/NN/<name>.txt` is listed under its file stem.
+/// With the `encrypted-inputs` feature, inputs in `data/inputs.enc` are listed as well, unless
+/// there is no key to decrypt them with.
+#[must_use]
+pub fn input_names(day: Day) -> Vec<String> {
+    input_names_in(&env::current_dir().unwrap().join("data"), day)
+}
+
+fn input_names_in(data_dir: &Path, day: Day) -> Vec<String> {
+    let input_dirs = [
+        Some(data_dir.join("inputs")),
+        encrypted_inputs_dir(data_dir, day),
+    ];
+
+    let mut has_default = false;
+    let mut named: Vec<String> = vec![];
+
+    for inputs_dir in input_dirs.into_iter().flatten() {
+        has_default |= inputs_dir.join(format!("{day}.txt")).is_file();
+
+        if let Ok(entries) = fs::read_dir(inputs_dir.join(day.to_string())) {
+            named.extend(
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                    .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                    .filter(|name| name != DEFAULT_INPUT_NAME),
+            );
+        }
+    }
+
+    named.sort();
+    named.dedup();
+
+    if has_default {
+        named.insert(0, DEFAULT_INPUT_NAME.to_string());
+    }
+
+    named
+}
+
+/// The folder of encrypted inputs, if they can be decrypted. Without a key, they are skipped
+/// with a notice, so tests still pass in forks that lack the secret.
+#[cfg(feature = "encrypted-inputs")]
+fn encrypted_inputs_dir(data_dir: &Path, day: Day) -> Option<PathBuf> {
+    let dir = data_dir.join("inputs.enc");
+    let has_inputs = dir.join(format!("{day}.txt")).is_file() || dir.join(day.to_string()).is_dir();
+
+    if has_inputs && !encryption::has_key() {
+        eprintln!(
+            "Skipping the encrypted inputs of day {day}, as {} is not set.",
+            encryption::KEY_ENV_VAR
+        );
+        return None;
+    }
+    Some(dir)
+}
+
+#[cfg(not(feature = "encrypted-inputs"))]
+fn encrypted_inputs_dir(_data_dir: &Path, _day: Day) -> Option<PathBuf> {
+    None
+}
+
+/// Reads the input with the given name, as listed by [`input_names`], applying the given normalisation policy.
+///
+/// Warns if the input is empty, lacks its trailing line break or has changed since it was downloaded
+/// or since its answers were recorded.
+#[must_use]
+pub fn read_input(day: Day, name: &str, normalization: Normalization) -> String {
+    let path = integrity::input_path(day, name);
+    let raw = read_bytes(&path).expect("could not open input file");
+    integrity::warn(day, name, &raw);
+    let raw = String::from_utf8(raw).expect("input file is not valid UTF-8");
+    normalize_contents(&raw, normalization, &path.display().to_string())
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// A trailing `normalize = <policy>` selects the [`Normalization`] applied to the input,
+/// e.g. `solution!(6, normalize = Normalization::PRESERVE)`. It defaults to [`Normalization::DEFAULT`]
+/// and is exposed as the constant `NORMALIZATION`.
+///
+/// A trailing `generate = <function>` registers a `fn(&mut Rng, usize) -> String` that generates
+/// inputs of a given size for `cargo gen` and `cargo time --scale`, e.g. `solution!(8, generate = generate)`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, normalize = $normalization:expr)? $(, generate = $generate:expr)?) => {
+        $crate::solution!(@impl $day, [$($normalization)?], [$($generate)?], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 1 $(, normalize = $normalization:expr)? $(, generate = $generate:expr)?) => {
+        $crate::solution!(@impl $day, [$($normalization)?], [$($generate)?], [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, 2 $(, normalize = $normalization:expr)? $(, generate = $generate:expr)?) => {
+        $crate::solution!(@impl $day, [$($normalization)?], [$($generate)?], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@normalization) => {
+        $crate::template::Normalization::DEFAULT
+    };
+    (@normalization $normalization:expr) => {
+        $normalization
+    };
+
+    (@generate) => {
+        None
+    };
+    (@generate $generate:expr) => {
+        Some($generate)
+    };
+
+    (@impl $day:expr, [$($normalization:tt)*], [$($generate:tt)*], $( [$func:ident, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The normalisation policy applied to this day's input.
+        #[allow(dead_code)]
+        const NORMALIZATION: $crate::template::Normalization = {
+            #[allow(unused_imports)]
+            use $crate::template::Normalization;
+            $crate::solution!(@normalization $($normalization)*)
+        };
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(not(feature = "dhat-heap"))]
+        #[global_allocator]
+        static ALLOC: $crate::template::memory::CountingAlloc = $crate::template::memory::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+
+            let generate: Option<Generator> = $crate::solution!(@generate $($generate)*);
+
+            if std::env::args().any(|x| x == "--generate") {
+                print_generated(DAY, generate);
+                return;
+            }
+
+            pin_to_core();
+
+            if std::env::args().any(|x| x == "--scale") {
+                $( scale_part($func, DAY, $part, generate); )*
+                return;
+            }
+
+            $crate::debug::init_from_args();
+            $crate::trace::init_from_args(DAY);
+            $crate::viz::init_from_args(DAY);
+
+            if std::env::args().any(|x| x == "--all-inputs") {
+                let mut all_match = true;
+                for name in $crate::template::input_names(DAY) {
+                    let input = $crate::template::read_input(DAY, &name, NORMALIZATION);
+                    println!("Input \"{name}\"");
+                    $( all_match &= check_part($func, &input, DAY, &name, $part); )*
+                }
+                if !all_match {
+                    std::process::exit(1);
+                }
+                return;
+            }
+
+            let input = $crate::template::read_input(
+                DAY,
+                $crate::template::DEFAULT_INPUT_NAME,
+                NORMALIZATION,
+            );
             $( run_part($func, &input, DAY, $part); )*
+            write_profile(DAY);
         }
+
+        /// Tests every input that has answers recorded in `data/answers.json`.
+        #[cfg(test)]
+        mod recorded_answers {
+            $(
+                #[test]
+                fn $func() {
+                    $crate::template::runner::assert_recorded_answers(
+                        super::$func,
+                        super::DAY,
+                        $part,
+                        super::NORMALIZATION,
+                    );
+                }
+            )*
+        }
+    };
+}
+
+/// Generates a test for every row of a table of examples.
+///
+/// Each row names the test, the function under test, the input source and the expected result.
+/// Further arguments can follow the source and are passed on to the function.
+/// Input sources are:
+///  - `example`: the example file `data/examples/NN.txt`.
+///  - `example(n)`: an additional example file `data/examples/NN-n.txt`.
+///  - `text("...")`: an inline example.
+///
+/// ```ignore
+/// advent_of_code::examples! {
+///     test_part_one: part_one(example) == Some(3);
+///     test_part_two: part_two(example(2)) == Some(6);
+///     test_part_one_short: part_one(text("L1\nR1\n")) == Some(0);
+///     test_part_one_limited: solve(example, 10) == Some(40);
+/// }
+/// ```
+#[macro_export]
+macro_rules! examples {
+    ($( $name:ident : $func:ident ( $kind:ident $( ( $($source:tt)* ) )? $(, $arg:expr)* ) == $expected:expr );* $(;)?) => {
+        #[cfg(test)]
+        mod examples {
+            $(
+                #[test]
+                fn $name() {
+                    let input = $crate::examples!(@source $kind $( ( $($source)* ) )?);
+                    assert_eq!(super::$func(&input $(, $arg)*), $expected);
+                }
+            )*
+        }
+    };
+
+    (@source example) => {
+        $crate::template::read_file_normalized("examples", super::DAY, super::NORMALIZATION)
     };
+    (@source example($part:expr)) => {
+        $crate::template::read_file_part_normalized(
+            "examples",
+            super::DAY,
+            $part,
+            super::NORMALIZATION,
+        )
+    };
+    (@source text($text:expr)) => {
+        super::NORMALIZATION.apply($text).into_owned()
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(feature = "test_lib", feature = "encrypted-inputs"))]
+mod tests {
+    use std::{env, fs};
+
+    use super::{Day, encryption, input_names_in};
+
+    #[test]
+    fn skips_encrypted_inputs_without_key() {
+        let data_dir = env::temp_dir().join(format!("aoc-input-names-{}", std::process::id()));
+        for path in [
+            "inputs/01.txt",
+            "inputs.enc/01.txt",
+            "inputs.enc/01/alice.txt",
+        ] {
+            let path = data_dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "L68\n").unwrap();
+        }
+
+        // SAFETY: no other test reads or writes the key
+        unsafe { env::remove_var(encryption::KEY_ENV_VAR) };
+        let names = input_names_in(&data_dir, Day::new(1).unwrap());
+        fs::remove_dir_all(&data_dir).unwrap();
+
+        assert_eq!(names, ["default"]);
+    }
 }
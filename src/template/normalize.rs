@@ -0,0 +1,189 @@
+/// Normalisation applied to puzzle inputs before they are handed to a solution.
+use std::borrow::Cow;
+
+const BOM: char = '\u{feff}';
+
+/// A normalisation policy for input files.
+///
+/// Policies are built from [`Normalization::PRESERVE`] by chaining the individual steps:
+///
+/// ```
+/// # use advent_of_code::template::Normalization;
+/// const POLICY: Normalization = Normalization::PRESERVE.strip_bom().normalize_crlf();
+/// assert_eq!(POLICY, Normalization::DEFAULT);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    strip_bom: bool,
+    normalize_crlf: bool,
+    strip_trailing_newline: bool,
+}
+
+impl Normalization {
+    /// Leaves the input exactly as it is stored on disk.
+    pub const PRESERVE: Self = Self {
+        strip_bom: false,
+        normalize_crlf: false,
+        strip_trailing_newline: false,
+    };
+
+    /// The policy used when a solution does not select one: strips a byte order mark
+    /// and converts CRLF line endings to LF. Trailing whitespace is left untouched.
+    pub const DEFAULT: Self = Self::PRESERVE.strip_bom().normalize_crlf();
+
+    /// Removes a leading UTF-8 byte order mark.
+    #[must_use]
+    pub const fn strip_bom(mut self) -> Self {
+        self.strip_bom = true;
+        self
+    }
+
+    /// Converts `\r\n` line endings to `\n`.
+    #[must_use]
+    pub const fn normalize_crlf(mut self) -> Self {
+        self.normalize_crlf = true;
+        self
+    }
+
+    /// Removes a single trailing line break, if present.
+    #[must_use]
+    pub const fn strip_trailing_newline(mut self) -> Self {
+        self.strip_trailing_newline = true;
+        self
+    }
+
+    /// Applies the policy to an input.
+    #[must_use]
+    pub fn apply<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        let mut s = Cow::Borrowed(input);
+
+        if self.strip_bom
+            && let Some(rest) = s.strip_prefix(BOM)
+        {
+            s = Cow::Owned(rest.to_string());
+        }
+
+        if self.normalize_crlf && s.contains("\r\n") {
+            s = Cow::Owned(s.replace("\r\n", "\n"));
+        }
+
+        if self.strip_trailing_newline {
+            let stripped = s
+                .strip_suffix("\r\n")
+                .or_else(|| s.strip_suffix('\n'))
+                .map(str::to_string);
+            if let Some(stripped) = stripped {
+                s = Cow::Owned(stripped);
+            }
+        }
+
+        s
+    }
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A character in an input that puzzle inputs usually do not contain.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnexpectedChar {
+    pub char: char,
+    pub count: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Finds characters that are not printable ASCII, spaces or line feeds.
+/// Each distinct character is reported once, with the position of its first occurrence (1-based).
+#[must_use]
+pub fn find_unexpected_chars(input: &str) -> Vec<UnexpectedChar> {
+    let mut found: Vec<UnexpectedChar> = vec![];
+
+    for (line_idx, line) in input.split('\n').enumerate() {
+        for (col_idx, char) in line.chars().enumerate() {
+            if char == ' ' || char.is_ascii_graphic() {
+                continue;
+            }
+
+            match found.iter_mut().find(|x| x.char == char) {
+                Some(existing) => existing.count += 1,
+                None => found.push(UnexpectedChar {
+                    char,
+                    count: 1,
+                    line: line_idx + 1,
+                    column: col_idx + 1,
+                }),
+            }
+        }
+    }
+
+    found
+}
+
+/// Prints a warning to stderr for every unexpected character in an input.
+pub fn warn_unexpected_chars(input: &str, origin: &str) {
+    for x in find_unexpected_chars(input) {
+        eprintln!(
+            "Warning: {origin} contains unexpected character {:?} (U+{:04X}) {} time(s), first at line {}, column {}.",
+            x.char, x.char as u32, x.count, x.line, x.column
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Normalization, UnexpectedChar, find_unexpected_chars};
+
+    const RAW: &str = "\u{feff}1 2  \r\n3 4\r\n";
+
+    #[test]
+    fn preserves_input() {
+        assert_eq!(Normalization::PRESERVE.apply(RAW), RAW);
+    }
+
+    #[test]
+    fn applies_default_policy() {
+        assert_eq!(Normalization::DEFAULT.apply(RAW), "1 2  \n3 4\n");
+    }
+
+    #[test]
+    fn strips_trailing_newline() {
+        let policy = Normalization::PRESERVE.strip_trailing_newline();
+        assert_eq!(policy.apply("a\n\n"), "a\n");
+        assert_eq!(policy.apply("a\r\n"), "a");
+        assert_eq!(policy.apply("a "), "a ");
+    }
+
+    #[test]
+    fn strips_everything() {
+        let policy = Normalization::DEFAULT.strip_trailing_newline();
+        assert_eq!(policy.apply(RAW), "1 2  \n3 4");
+    }
+
+    #[test]
+    fn finds_unexpected_chars() {
+        assert_eq!(
+            find_unexpected_chars(RAW),
+            vec![
+                UnexpectedChar {
+                    char: '\u{feff}',
+                    count: 1,
+                    line: 1,
+                    column: 1
+                },
+                UnexpectedChar {
+                    char: '\r',
+                    count: 2,
+                    line: 1,
+                    column: 7
+                },
+            ]
+        );
+        assert_eq!(find_unexpected_chars("1 2  \n3 4\n"), vec![]);
+    }
+}
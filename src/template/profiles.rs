@@ -0,0 +1,193 @@
+/// Profiles written by `cargo solve <day> --dhat` and `--profile`, and summaries of their allocation sites.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+/// The directory profiles are written to.
+pub fn dir() -> PathBuf {
+    Path::new("data").join("profiles")
+}
+
+/// Where the DHAT heap profile of a part is written.
+pub fn dhat_path(day: Day, part: u8) -> PathBuf {
+    dir().join(format!("{day}-part{part}-dhat.json"))
+}
+
+/// Where the flame graph of a day's CPU profile is written.
+#[cfg(all(feature = "cpu-profile", unix))]
+pub fn flamegraph_path(day: Day) -> PathBuf {
+    dir().join(format!("{day}.svg"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Allocations made from one call stack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AllocationSite {
+    pub total_bytes: usize,
+    pub total_blocks: usize,
+    /// The most bytes held at once by allocations from this site.
+    pub max_bytes: usize,
+    /// The call stack, innermost frame first.
+    pub frames: Vec<String>,
+}
+
+impl AllocationSite {
+    /// The innermost frame in the solution or the template, skipping frames in the standard
+    /// library and dependencies. Falls back to the innermost frame.
+    pub fn location(&self) -> &str {
+        let frame = self
+            .frames
+            .iter()
+            .find(|frame| frame.contains("src/bin/") || frame.contains("advent_of_code::"))
+            .or(self.frames.first())
+            .map_or("[unknown]", String::as_str);
+        // frames start with the instruction address, e.g. `0x5634a1: main (src/main.rs:1:1)`
+        frame.split_once(": ").map_or(frame, |(_, name)| name)
+    }
+}
+
+/// The allocation sites of a DHAT heap profile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DhatProfile {
+    /// Sites ordered by total bytes allocated, most first.
+    pub sites: Vec<AllocationSite>,
+}
+
+impl DhatProfile {
+    pub fn read_file(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let json = JsonValue::from_str(&contents)
+            .map_err(|_| format!("{}: not valid JSON", path.display()))?;
+        Self::try_from(&json).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.sites.iter().map(|site| site.total_bytes).sum()
+    }
+
+    pub fn total_blocks(&self) -> usize {
+        self.sites.iter().map(|site| site.total_blocks).sum()
+    }
+}
+
+impl TryFrom<&JsonValue> for DhatProfile {
+    type Error = String;
+
+    /// Reads DHAT's JSON format, see `dhat/dh_main.c` in Valgrind's sources.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected profile to be a JSON object.")?;
+
+        let frame_table: Vec<&String> = json
+            .get("ftbl")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected profile.ftbl to be an array.")?
+            .iter()
+            .map(|frame| frame.get::<String>())
+            .collect::<Option<_>>()
+            .ok_or("Expected profile.ftbl to contain strings.")?;
+
+        let program_points = json
+            .get("pps")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected profile.pps to be an array.")?;
+
+        let mut sites = program_points
+            .iter()
+            .map(|point| {
+                let point = point
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected program point to be an object.")?;
+                let number = |key: &str| {
+                    point
+                        .get(key)
+                        .and_then(|v| v.get::<f64>().copied())
+                        .map(|v| v as usize)
+                };
+                let frames = point
+                    .get("fs")
+                    .and_then(|v| v.get::<Vec<JsonValue>>())
+                    .ok_or("Expected program point.fs to be an array.")?
+                    .iter()
+                    .map(|idx| {
+                        let idx = *idx.get::<f64>()? as usize;
+                        frame_table.get(idx).map(|frame| (*frame).clone())
+                    })
+                    .collect::<Option<Vec<String>>>()
+                    .ok_or("Expected program point.fs to index profile.ftbl.")?;
+
+                Ok(AllocationSite {
+                    total_bytes: number("tb").ok_or("Expected program point.tb to be a number.")?,
+                    total_blocks: number("tbk")
+                        .ok_or("Expected program point.tbk to be a number.")?,
+                    max_bytes: number("mb").unwrap_or(0),
+                    frames,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        sites.sort_by_key(|site| std::cmp::Reverse(site.total_bytes));
+        Ok(DhatProfile { sites })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{DhatProfile, dhat_path};
+    use crate::day;
+
+    const PROFILE: &str = r#"{
+        "dhatFileVersion": 2, "mode": "rust-heap",
+        "pps": [
+            { "tb": 64, "tbk": 2, "mb": 32, "fs": [1, 3] },
+            { "tb": 4096, "tbk": 1, "mb": 4096, "fs": [1, 2, 3] }
+        ],
+        "ftbl": [
+            "[root]",
+            "0x1: alloc::raw_vec::finish_grow (alloc/src/raw_vec.rs:1:1)",
+            "0x2: _08::parse_input (src/bin/08.rs:10:5)",
+            "0x3: _08::main (src/bin/08.rs:4:1)"
+        ]
+    }"#;
+
+    #[test]
+    fn reads_allocation_sites() {
+        let profile = DhatProfile::try_from(&JsonValue::from_str(PROFILE).unwrap()).unwrap();
+
+        assert_eq!(profile.total_bytes(), 4160);
+        assert_eq!(profile.total_blocks(), 3);
+        assert_eq!(profile.sites[0].total_bytes, 4096);
+        assert_eq!(
+            profile.sites[0].location(),
+            "_08::parse_input (src/bin/08.rs:10:5)"
+        );
+        assert_eq!(profile.sites[1].location(), "_08::main (src/bin/08.rs:4:1)");
+    }
+
+    #[test]
+    fn names_profiles() {
+        assert_eq!(
+            dhat_path(day!(8), 2),
+            std::path::Path::new("data/profiles/08-part2-dhat.json")
+        );
+        #[cfg(all(feature = "cpu-profile", unix))]
+        assert_eq!(
+            super::flamegraph_path(day!(8)),
+            std::path::Path::new("data/profiles/08.svg")
+        );
+    }
+}
@@ -3,7 +3,8 @@
 use std::{fs, io};
 
 use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::memory::{MemoryUsage, format_bytes};
+use crate::template::timings::{Timing, Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -52,26 +53,101 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
+fn format_memory(memory: Option<MemoryUsage>) -> String {
+    memory.map_or_else(
+        || "-".into(),
+        |memory| {
+            format!(
+                "`{}` peak, {} in {} allocs",
+                format_bytes(memory.peak_bytes),
+                format_bytes(memory.total_bytes),
+                memory.allocations
+            )
+        },
+    )
+}
+
+/// Where the timings were taken, e.g. `AMD EPYC 7B13 with rustc 1.91.0`.
+fn describe_machine(timing: &Timing) -> Option<String> {
+    match (&timing.cpu_model, &timing.rustc_version) {
+        (Some(cpu), Some(rustc)) => Some(format!("{cpu} with {rustc}")),
+        (Some(cpu), None) => Some(cpu.clone()),
+        (None, Some(rustc)) => Some(format!("an unknown CPU with {rustc}")),
+        (None, None) => None,
+    }
+}
+
+/// A line naming the machines the timings were taken on, and which days were timed on each
+/// if there are several.
+fn machine_line(timings: &Timings) -> Option<String> {
+    let mut machines: Vec<(String, Vec<Day>)> = vec![];
+    for timing in &timings.data {
+        let Some(machine) = describe_machine(timing) else {
+            continue;
+        };
+        match machines.iter_mut().find(|(m, _)| *m == machine) {
+            Some((_, days)) => days.push(timing.day),
+            None => machines.push((machine, vec![timing.day])),
+        }
+    }
+
+    let described = match &machines[..] {
+        [] => return None,
+        [(machine, _)] => machine.clone(),
+        machines => machines
+            .iter()
+            .map(|(machine, days)| {
+                let days: Vec<String> = days
+                    .iter()
+                    .map(|day| day.into_inner().to_string())
+                    .collect();
+                let noun = if days.len() == 1 { "day" } else { "days" };
+                format!("{machine} ({noun} {})", days.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+    };
+    Some(format!("_Measured on {described}._"))
+}
+
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    // memory columns only appear once some day was timed with `--memory`
+    let with_memory = timings
+        .data
+        .iter()
+        .any(|timing| timing.part_1_memory.is_some() || timing.part_2_memory.is_some());
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+    if let Some(machine) = machine_line(&timings) {
+        lines.push(machine);
+        lines.push(String::new());
+    }
+    if with_memory {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |".into());
+        lines.push("| :---: | :---: | :---:  | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        );
+        if with_memory {
+            line += &format!(
+                " {} | {} |",
+                format_memory(timing.part_1_memory),
+                format_memory(timing.part_2_memory)
+            );
+        }
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -100,7 +176,9 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::memory::MemoryUsage, template::timings::Timing, template::timings::Timings,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -110,18 +188,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Default::default()
                 },
             ],
         }
@@ -180,4 +261,52 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(MemoryUsage {
+            peak_bytes: 1536,
+            total_bytes: 4096,
+            allocations: 3,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3],
+            "| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.5 KiB` peak, 4.0 KiB in 3 allocs | - |"
+        );
+        assert!(lines[6].ends_with("| - | - |"));
+    }
+
+    #[test]
+    fn format_machine() {
+        let mut timings = get_mock_timings();
+        for timing in &mut timings.data {
+            timing.cpu_model = Some("AMD EPYC 7B13".into());
+            timing.rustc_version = Some("rustc 1.91.0".into());
+        }
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings.clone(), 190.0).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[1], "## Benchmarks");
+        assert_eq!(lines[3], "_Measured on AMD EPYC 7B13 with rustc 1.91.0._");
+        assert_eq!(lines[5], "| Day | Part 1 | Part 2 |");
+
+        timings.data[2].rustc_version = None;
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3],
+            "_Measured on AMD EPYC 7B13 with rustc 1.91.0 (days 1, 2); AMD EPYC 7B13 (day 4)._"
+        );
+    }
 }
@@ -7,7 +7,14 @@ use super::{
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    track_memory: bool,
+    count_events: bool,
+    pin: Option<usize>,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,7 +31,15 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = child_commands::run_solution(
+                day,
+                is_timed,
+                is_release,
+                track_memory,
+                count_events,
+                pin,
+            )
+            .unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
@@ -68,7 +83,7 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::{Day, counters::HardwareCounters, memory::MemoryUsage};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -77,25 +92,52 @@ pub mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        track_memory: bool,
+        count_events: bool,
+        pin: Option<usize>,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
         let day_padded = day.to_string();
+        let core = pin.map(|core| core.to_string());
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
         if is_release {
             args.push("--release");
         }
 
+        if count_events {
+            args.extend(["--features", "perf-counters"]);
+        }
+
+        if is_timed || track_memory || count_events || pin.is_some() {
+            args.push("--");
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if track_memory {
+            args.push("--memory");
+        }
+
+        if count_events {
+            args.push("--counters");
+        }
+
+        if let Some(core) = &core {
+            args.extend(["--pin", core]);
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -131,9 +173,7 @@ pub mod child_commands {
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+            ..Default::default()
         };
 
         output
@@ -149,13 +189,17 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, l))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, line)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_memory = parse_measurement(line, MemoryUsage::parse);
+                    timings.part_1_counters = parse_measurement(line, HardwareCounters::parse);
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_memory = parse_measurement(line, MemoryUsage::parse);
+                    timings.part_2_counters = parse_measurement(line, HardwareCounters::parse);
                 }
 
                 timings.total_nanos += nanos;
@@ -164,6 +208,15 @@ pub mod child_commands {
         timings
     }
 
+    /// Parses a bracketed measurement printed after the timing, e.g. the heap usage with
+    /// `--memory` or the hardware counters with `--counters`.
+    fn parse_measurement<T>(line: &str, parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+        let (_, measurements) = line.rsplit_once(" samples) ")?;
+        measurements
+            .split_inclusive(']')
+            .find_map(|measurement| parse(measurement.trim()))
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -222,6 +275,42 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1_memory, None);
+        }
+
+        #[test]
+        fn parses_memory_usage() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [2048 B peak · 4096 B total · 3 allocs]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            assert_eq!(res.part_1_memory.unwrap().peak_bytes, 2048);
+            assert_eq!(res.part_1_memory.unwrap().allocations, 3);
+            assert_eq!(res.part_2_memory, None);
+        }
+
+        #[test]
+        fn parses_hardware_counters() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [2048 B peak · 4096 B total · 3 allocs] [9000 instrs · 4000 cycles · 2.25 IPC · 12 cache misses · 34 branch misses]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples) [9000 instrs · 3000 cycles · 3.00 IPC · 0 cache misses · 1 branch misses]"
+                        .into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1_memory.unwrap().allocations, 3);
+            assert_eq!(res.part_1_counters.unwrap().cache_misses, 12);
+            assert_eq!(res.part_2_memory, None);
+            assert_eq!(res.part_2_counters.unwrap().cycles, 3000);
         }
 
         #[test]
@@ -6,51 +6,343 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::rng::Rng;
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::Answers;
+use crate::template::counters::{Counters, HardwareCounters};
+use crate::template::integrity;
+use crate::template::machine;
+use crate::template::memory::{self, MemoryUsage};
+#[cfg(any(feature = "dhat-heap", all(feature = "cpu-profile", unix)))]
+use crate::template::profiles;
+use crate::template::scaling;
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, DEFAULT_GENERATED_SEED, DEFAULT_GENERATED_SIZE, Day, Normalization,
+    aoc_cli, input_names, read_input,
+};
+#[cfg(all(feature = "cpu-profile", unix))]
+use crate::template::{flamegraph, sampler};
+use crate::{debug, trace, viz};
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
+    trace::begin_part(part);
+    viz::begin_part(part);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples, counters) = run_timed(&func, input, day, part, |result| {
+        print_result(result, &part_str, "");
+    });
+    let memory = measure_memory(&func, input).map(|usage| format!(" {usage}"));
+    let counters = counters.map(|counters| format!(" {counters}"));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &result,
+        &part_str,
+        &(format_duration(&duration, samples)
+            + memory.as_deref().unwrap_or("")
+            + counters.as_deref().unwrap_or("")),
+    );
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run a solution part against a named input and compare the result with the answer recorded in `data/answers.json`.
+/// Returns `false` if the result differs from the recorded answer, or if there is no result although
+/// an answer is recorded.
+///
+/// If `--record` is passed, results for inputs without a recorded answer are stored.
+pub fn check_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    input_name: &str,
+    part: u8,
+) -> bool {
+    let part_str = format!("Part {part}");
+    let mut answers = Answers::read_from_file();
+
+    let Some(result) = func(input).map(|x| x.to_string()) else {
+        // no result is only a failure if there is an answer it should have matched
+        return match answers.get(day, input_name, part) {
+            Some(expected) => {
+                println!("{part_str}: ✖ {ANSI_ITALIC}expected {expected}{ANSI_RESET}");
+                false
+            }
+            None => {
+                println!("{part_str}: ✖");
+                true
+            }
+        };
+    };
+
+    match answers.get(day, input_name, part) {
+        Some(expected) if expected == result => {
+            println!("{part_str}: {ANSI_BOLD}{result}{ANSI_RESET} ✔");
+            true
+        }
+        Some(expected) => {
+            println!(
+                "{part_str}: {ANSI_BOLD}{result}{ANSI_RESET} ✖ {ANSI_ITALIC}expected {expected}{ANSI_RESET}"
+            );
+            false
+        }
+        None if env::args().any(|x| x == "--record") => {
+            answers.set(day, input_name, part, result.clone());
+            if let Ok(checksum) = integrity::read_checksum(day, input_name) {
+                answers.set_input_sha256(day, input_name, checksum.sha256);
+            }
+            if let Err(e) = answers.store_file() {
+                eprintln!("Failed to store answers: {e}");
+                process::exit(1);
+            }
+            println!(
+                "{part_str}: {ANSI_BOLD}{result}{ANSI_RESET} {ANSI_ITALIC}(recorded){ANSI_RESET}"
+            );
+            true
+        }
+        None => {
+            println!(
+                "{part_str}: {ANSI_BOLD}{result}{ANSI_RESET} {ANSI_ITALIC}(no recorded answer){ANSI_RESET}"
+            );
+            true
+        }
+    }
+}
+
+/// Run a solution part against every input of a day that has a recorded answer and panic on any mismatch.
+/// Used by the `recorded_answers` tests generated by the `solution!` macro.
+pub fn assert_recorded_answers<T: Display>(
+    func: impl Fn(&str) -> Option<T>,
+    day: Day,
+    part: u8,
+    normalization: Normalization,
+) {
+    let answers = Answers::read_from_file();
+
+    let mismatches: Vec<String> = input_names(day)
+        .iter()
+        .filter_map(|name| {
+            let expected = answers.get(day, name, part)?;
+            let input = read_input(day, name, normalization);
+            let result = func(&input).map(|x| x.to_string());
+
+            if result.as_deref() == Some(expected) {
+                None
+            } else {
+                Some(format!(
+                    "input \"{name}\": expected {expected}, got {}",
+                    result.as_deref().unwrap_or("None")
+                ))
+            }
+        })
+        .collect();
+
+    assert!(
+        mismatches.is_empty(),
+        "Part {part} does not match the recorded answers:\n{}",
+        mismatches.join("\n")
+    );
+}
+
+/// A day's input generator, registered with `generate = <function>` in its `solution!` call.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// Print an input generated for the size and seed passed with `--size` and `--seed`.
+pub fn print_generated(day: Day, generate: Option<Generator>) {
+    let generate = generator_or_exit(day, generate);
+    let size = flag_value("--size", DEFAULT_GENERATED_SIZE as u64) as usize;
+    let seed = flag_value("--seed", DEFAULT_GENERATED_SEED);
+    print!("{}", generate(&mut Rng::new(seed), size));
+}
+
+/// Time a solution part on generated inputs of doubling size, then print a chart of the timings
+/// and the exponent of their growth.
+pub fn scale_part<T>(
+    func: impl Fn(&str) -> Option<T>,
+    day: Day,
+    part: u8,
+    generate: Option<Generator>,
+) {
+    let generate = generator_or_exit(day, generate);
+    let seed = flag_value("--seed", DEFAULT_GENERATED_SEED);
+    let mut stdout = stdout();
+
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+    let mut measurements = vec![];
+    for size in scaling::sizes() {
+        print!("\r{ANSI_ITALIC}measuring size {size}{ANSI_RESET}");
+        let _ = stdout.flush();
+
+        let input = generate(&mut Rng::new(seed), size);
+        let timer = Instant::now();
+        black_box(func(black_box(&input)));
+        let base_time = timer.elapsed();
+
+        // repeat fast runs for about 100ms to even out noise
+        let iterations = (Duration::from_millis(100).as_nanos()
+            / cmp::max(base_time.as_nanos(), 10))
+        .clamp(1, 1000);
+        let duration = if iterations > 1 {
+            average_time(&func, &*input, iterations, None)
+        } else {
+            base_time
+        };
+
+        measurements.push(scaling::Measurement { size, duration });
+        if base_time > scaling::TIME_LIMIT {
+            break;
+        }
+    }
+
+    print!("\r\x1b[2K");
+    print!("{}", scaling::chart(&measurements));
+    match scaling::fit_exponent(&measurements) {
+        Some(exponent) => println!(
+            "Grows like {ANSI_BOLD}n^{exponent:.2}{ANSI_RESET} {ANSI_ITALIC}({}){ANSI_RESET}",
+            scaling::describe(exponent)
+        ),
+        None => println!("{ANSI_ITALIC}Too few measurements to fit an exponent.{ANSI_RESET}"),
+    }
+}
+
+fn generator_or_exit(day: Day, generate: Option<Generator>) -> Generator {
+    generate.unwrap_or_else(|| {
+        eprintln!(
+            "Day {day} has no input generator. Register one with `generate = <function>` in its `solution!` call."
+        );
+        process::exit(1);
+    })
+}
+
+/// The number following a flag, e.g. `--size 100`, or the default if the flag is not passed.
+fn flag_value(flag: &str, default: u64) -> u64 {
+    let args: Vec<String> = env::args().collect();
+    match args.iter().position(|x| x == flag) {
+        Some(idx) => args
+            .get(idx + 1)
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_else(|| {
+                eprintln!("Unexpected command-line input. Format: {flag} <n>");
+                process::exit(1);
+            }),
+        None => default,
+    }
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With `--profile`, the call stack is sampled while the function is benched.
+/// With `--counters`, hardware events are counted while the function is benched.
+/// If the first run printed debug output, recorded a trace or drew frames, it is timed again
+/// without them.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    day: Day,
+    part: u8,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<HardwareCounters>) {
+    #[cfg(not(feature = "dhat-heap"))]
+    let _ = (day, part);
+
     let timer = Instant::now();
     let result = {
+        // one profile per part, written when the profiler is dropped
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = {
+            let _ = std::fs::create_dir_all(profiles::dir());
+            dhat::Profiler::builder()
+                .file_name(profiles::dhat_path(day, part))
+                .build()
+        };
 
         func(input)
     };
-    let base_time = timer.elapsed();
+    let mut base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    if debug::level() > 0 || trace::enabled() || viz::enabled() {
+        let _paused = (debug::pause(), trace::pause(), viz::pause());
+        base_time = average_time(&func, input, 1, None);
+    }
+
+    let profile = env::args().any(|x| x == "--profile");
+    let run = if profile || env::args().any(|x| x == "--time") {
+        let _paused = (debug::pause(), trace::pause(), viz::pause());
+        #[cfg(all(feature = "cpu-profile", unix))]
+        let _sampling = profile.then(sampler::start);
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        (base_time, 1, None)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, run.2)
+}
+
+/// Write the call stacks sampled while benching the parts as a flame graph, if `--profile` was passed.
+pub fn write_profile(day: Day) {
+    if !env::args().any(|x| x == "--profile") {
+        return;
+    }
+
+    #[cfg(all(feature = "cpu-profile", unix))]
+    {
+        let samples = sampler::collect();
+        if samples.dropped > 0 {
+            eprintln!(
+                "{} samples did not fit into the buffer and were dropped.",
+                samples.dropped
+            );
+        }
+
+        let title = format!(
+            "Day {day} · {} samples at {:.0} Hz",
+            samples.count(),
+            samples.rate
+        );
+        let path = profiles::flamegraph_path(day);
+        let svg = flamegraph::render(&title, &samples.stacks);
+        match std::fs::create_dir_all(profiles::dir()).and_then(|()| std::fs::write(&path, svg)) {
+            Ok(()) => println!("Saved flame graph to {}", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write {}: {e}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    #[cfg(not(all(feature = "cpu-profile", unix)))]
+    eprintln!(
+        "Day {day} was not profiled: sampling needs the `cpu-profile` feature on a unix system."
+    );
+}
+
+/// Count the heap usage of one more run, if `--memory` was passed.
+fn measure_memory<I: Copy, T>(func: impl Fn(I) -> T, input: I) -> Option<MemoryUsage> {
+    if !env::args().any(|x| x == "--memory") {
+        return None;
+    }
+    if cfg!(feature = "dhat-heap") {
+        eprintln!("Memory usage is not counted with the `dhat-heap` feature.");
+        return None;
+    }
+
+    let _paused = (debug::pause(), trace::pause(), viz::pause());
+    let (_, usage) = memory::measure(|| black_box(func(black_box(input))));
+    Some(usage)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128, Option<HardwareCounters>) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -59,19 +351,65 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    let mut counters = open_counters();
+
+    (
+        average_time(func, input, bench_iterations, counters.as_mut()),
+        bench_iterations,
+        counters.map(|counters| counters.per_iteration()),
+    )
+}
+
+/// Pin the thread that runs the solution to the core passed with `--pin <core>`.
+/// Called by the `solution!` macro before the first timed run, which the number of bench
+/// iterations is derived from.
+pub fn pin_to_core() {
+    if !env::args().any(|x| x == "--pin") {
+        return;
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let core = flag_value("--pin", 0) as usize;
+    if let Err(e) = machine::pin_thread(core) {
+        eprintln!("Benchmarks are not pinned to core {core}: {e}");
+    }
+}
+
+/// Open the hardware counters, if `--counters` was passed.
+fn open_counters() -> Option<Counters> {
+    if !env::args().any(|x| x == "--counters") {
+        return None;
+    }
+
+    Counters::open()
+        .map_err(|e| eprintln!("Hardware events are not counted: {e}"))
+        .ok()
+}
+
+/// Average the time of several runs. Counters are started before and stopped after each run,
+/// outside of the timed section.
+fn average_time<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    iterations: u128,
+    mut counters: Option<&mut Counters>,
+) -> Duration {
     let mut timers: Vec<Duration> = vec![];
 
-    for _ in 0..bench_iterations {
+    for _ in 0..iterations {
+        if let Some(counters) = &counters {
+            counters.start();
+        }
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
+        if let Some(counters) = &mut counters {
+            counters.stop();
+        }
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    #[allow(clippy::cast_possible_truncation)]
+    Duration::from_nanos(average_duration(&timers) as u64)
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
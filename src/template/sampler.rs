@@ -0,0 +1,190 @@
+/// Samples the call stack at a fixed rate, for `cargo solve <day> --profile`.
+///
+/// A `SIGPROF` timer interrupts the process after every few milliseconds of CPU time. The signal
+/// handler copies the instruction pointers of the interrupted stack into preallocated buffers,
+/// as it may not allocate. They are resolved to function names after sampling stops.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use std::{mem, ptr};
+
+use crate::template::flamegraph::Stacks;
+
+/// Samples per second of CPU time that are asked for, slightly off a round number so sampling
+/// does not run in lockstep with periodic work of the solution. The kernel delivers the signal
+/// at most once per scheduler tick, so the actual rate can be lower.
+const FREQUENCY: u32 = 997;
+
+/// Samples beyond this many are dropped, about 16 seconds of CPU time.
+const MAX_SAMPLES: usize = 16384;
+/// Frames beyond this depth are cut off, counting from the innermost frame.
+const MAX_DEPTH: usize = 64;
+
+static SAMPLES: [[AtomicUsize; MAX_DEPTH]; MAX_SAMPLES] =
+    [const { [const { AtomicUsize::new(0) }; MAX_DEPTH] }; MAX_SAMPLES];
+static DEPTHS: [AtomicUsize; MAX_SAMPLES] = [const { AtomicUsize::new(0) }; MAX_SAMPLES];
+/// The number of signals handled, including dropped samples.
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+/// The CPU time spent while sampling, in nanoseconds.
+static CPU_TIME: AtomicU64 = AtomicU64::new(0);
+
+/// Samples the call stack while it is alive.
+pub struct Sampling {
+    started: Duration,
+}
+
+/// Starts sampling the call stack. Samples collect until [`collect`] is called.
+pub fn start() -> Sampling {
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = handle_sigprof as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&raw mut action.sa_mask);
+        libc::sigaction(libc::SIGPROF, &raw const action, ptr::null_mut());
+    }
+    set_timer(1_000_000 / FREQUENCY);
+    Sampling {
+        started: cpu_time(),
+    }
+}
+
+impl Drop for Sampling {
+    fn drop(&mut self) {
+        set_timer(0);
+        let elapsed = cpu_time().saturating_sub(self.started);
+        CPU_TIME.fetch_add(
+            u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+/// The CPU time the process has used so far.
+fn cpu_time() -> Duration {
+    let mut time: libc::timespec = unsafe { mem::zeroed() };
+    unsafe {
+        libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &raw mut time);
+    }
+    Duration::new(
+        u64::try_from(time.tv_sec).unwrap_or(0),
+        u32::try_from(time.tv_nsec).unwrap_or(0),
+    )
+}
+
+/// Sets the interval of the `SIGPROF` timer, 0 to stop it.
+#[allow(clippy::cast_possible_wrap)]
+fn set_timer(micros: u32) {
+    let interval = libc::timeval {
+        tv_sec: 0,
+        tv_usec: micros as libc::suseconds_t,
+    };
+    let timer = libc::itimerval {
+        it_interval: interval,
+        it_value: interval,
+    };
+    unsafe {
+        libc::setitimer(libc::ITIMER_PROF, &raw const timer, ptr::null_mut());
+    }
+}
+
+#[inline(never)]
+extern "C" fn handle_sigprof(_: libc::c_int) {
+    let idx = COUNT.fetch_add(1, Ordering::Relaxed);
+    if idx >= MAX_SAMPLES {
+        return;
+    }
+
+    let mut depth = 0;
+    // SAFETY: the process is single-threaded while solutions run, so no other thread unwinds.
+    unsafe {
+        backtrace::trace_unsynchronized(|frame| {
+            SAMPLES[idx][depth].store(frame.ip() as usize, Ordering::Relaxed);
+            depth += 1;
+            depth < MAX_DEPTH
+        });
+    }
+    DEPTHS[idx].store(depth, Ordering::Relaxed);
+}
+
+/// The stacks sampled since the first call to [`start`].
+pub struct Samples {
+    pub stacks: Stacks,
+    /// Samples that did not fit into the buffers.
+    pub dropped: usize,
+    /// Samples taken per second of CPU time, including dropped ones.
+    pub rate: f64,
+}
+
+impl Samples {
+    pub fn count(&self) -> usize {
+        self.stacks.values().sum()
+    }
+}
+
+/// Resolves the sampled stacks to function names, outermost first.
+///
+/// Frames of the signal handler are removed, as are the frames of the runner up to the call of
+/// the solution, so stacks start at `part_one` or `part_two`.
+pub fn collect() -> Samples {
+    let count = COUNT.load(Ordering::Relaxed);
+    let mut names: HashMap<usize, Vec<String>> = HashMap::new();
+    let mut stacks = Stacks::new();
+
+    for (sample, depth) in SAMPLES.iter().zip(&DEPTHS).take(count.min(MAX_SAMPLES)) {
+        let mut stack: Vec<String> = sample[..depth.load(Ordering::Relaxed)]
+            .iter()
+            .flat_map(|ip| {
+                let ip = ip.load(Ordering::Relaxed);
+                names.entry(ip).or_insert_with(|| resolve(ip)).clone()
+            })
+            .collect();
+
+        if let Some(handler) = stack
+            .iter()
+            .position(|name| name.contains("handle_sigprof"))
+        {
+            stack.drain(..=handler);
+        }
+        stack.retain(|name| name != "__restore_rt" && !name.starts_with("core::ops::function::"));
+        stack.reverse();
+
+        if let Some(runner) = stack.iter().rposition(|name| name.contains("runner::")) {
+            stack.drain(..=runner);
+        }
+        if !stack.is_empty() {
+            *stacks.entry(stack).or_default() += 1;
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let seconds = CPU_TIME.load(Ordering::Relaxed) as f64 / 1e9;
+    #[allow(clippy::cast_precision_loss)]
+    let rate = if seconds > 0.0 {
+        count as f64 / seconds
+    } else {
+        0.0
+    };
+
+    Samples {
+        stacks,
+        dropped: count.saturating_sub(MAX_SAMPLES),
+        rate,
+    }
+}
+
+/// The names of the function at an instruction address and of the functions inlined into it,
+/// innermost first.
+fn resolve(ip: usize) -> Vec<String> {
+    let mut names = vec![];
+    backtrace::resolve(ip as *mut _, |symbol| {
+        names.push(
+            symbol
+                .name()
+                .map_or_else(|| format!("{ip:#x}"), |name| format!("{name:#}")),
+        );
+    });
+    if names.is_empty() {
+        names.push(format!("{ip:#x}"));
+    }
+    names
+}
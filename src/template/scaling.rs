@@ -0,0 +1,153 @@
+/// Estimates how the running time of a solution grows with the size of its input,
+/// from timings on generated inputs of geometrically increasing size.
+use std::iter;
+use std::time::Duration;
+
+/// The size of the smallest generated input.
+pub const FIRST_SIZE: usize = 16;
+
+/// Sizes double until a single run takes longer than [`TIME_LIMIT`], or up to this size.
+pub const MAX_SIZE: usize = 1 << 20;
+
+/// A run that takes longer than this ends the measurements.
+pub const TIME_LIMIT: Duration = Duration::from_secs(1);
+
+/// The exponent is fitted over this many of the largest sizes, where constant overheads matter least.
+const FIT_POINTS: usize = 5;
+
+const CHART_WIDTH: usize = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub size: usize,
+    pub duration: Duration,
+}
+
+/// The sizes to measure, in increasing order.
+pub fn sizes() -> impl Iterator<Item = usize> {
+    iter::successors(Some(FIRST_SIZE), |size| Some(size * 2)).take_while(|size| *size <= MAX_SIZE)
+}
+
+/// Fits `time = c · size^k` by least squares on a log-log scale and returns `k`.
+/// Needs at least two measurements.
+#[allow(clippy::cast_precision_loss)]
+pub fn fit_exponent(measurements: &[Measurement]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = measurements
+        .iter()
+        .rev()
+        .take(FIT_POINTS)
+        .filter(|measurement| !measurement.duration.is_zero())
+        .map(|measurement| {
+            (
+                (measurement.size as f64).ln(),
+                measurement.duration.as_secs_f64().ln(),
+            )
+        })
+        .collect();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    let len = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / len;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / len;
+    let (covariance, variance) = points.iter().fold((0.0, 0.0), |(cov, var), (x, y)| {
+        (
+            cov + (x - mean_x) * (y - mean_y),
+            var + (x - mean_x).powi(2),
+        )
+    });
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// A rough name for the complexity class of a fitted exponent.
+pub fn describe(exponent: f64) -> &'static str {
+    match exponent {
+        e if e < 0.5 => "constant or logarithmic",
+        e if e < 1.3 => "linear or n log n",
+        e if e < 1.7 => "between linear and quadratic",
+        e if e < 2.5 => "quadratic",
+        e if e < 3.5 => "cubic",
+        _ => "worse than cubic",
+    }
+}
+
+/// A table of sizes and times with a bar for each size. The length of the bars grows with the
+/// logarithm of the time, so a straight edge means polynomial growth and its slope the exponent.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn chart(measurements: &[Measurement]) -> String {
+    let log_nanos =
+        |measurement: &Measurement| (measurement.duration.as_nanos().max(1) as f64).ln();
+    let min = measurements
+        .iter()
+        .map(log_nanos)
+        .fold(f64::INFINITY, f64::min);
+    let max = measurements
+        .iter()
+        .map(log_nanos)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let mut chart = format!("{:>9}  {:>10}\n", "size", "time");
+    for measurement in measurements {
+        let fraction = if max > min {
+            (log_nanos(measurement) - min) / (max - min)
+        } else {
+            1.0
+        };
+        let bar = 1 + (fraction * (CHART_WIDTH - 1) as f64).round() as usize;
+        let time = format!("{:.1?}", measurement.duration);
+        chart += &format!("{:>9}  {time:>10}  {}\n", measurement.size, "█".repeat(bar));
+    }
+    chart
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Measurement, chart, describe, fit_exponent, sizes};
+
+    fn measurements(growth: impl Fn(f64) -> f64) -> Vec<Measurement> {
+        sizes()
+            .take(8)
+            .map(|size| Measurement {
+                size,
+                duration: Duration::from_secs_f64(growth(size as f64) * 1e-9),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_exponents() {
+        let linear = fit_exponent(&measurements(|n| 40.0 * n)).unwrap();
+        assert!((linear - 1.0).abs() < 0.01, "{linear}");
+
+        let quadratic = fit_exponent(&measurements(|n| 3.0 * n * n + 100.0)).unwrap();
+        assert!((quadratic - 2.0).abs() < 0.05, "{quadratic}");
+        assert_eq!(describe(quadratic), "quadratic");
+
+        let n_log_n = fit_exponent(&measurements(|n| 10.0 * n * n.ln())).unwrap();
+        assert_eq!(describe(n_log_n), "linear or n log n");
+
+        assert_eq!(fit_exponent(&measurements(|n| n)[..1]), None);
+    }
+
+    #[test]
+    fn draws_chart() {
+        let chart = chart(&measurements(|n| 1000.0 * n));
+        let lines: Vec<&str> = chart.lines().collect();
+
+        assert_eq!(lines.len(), 9);
+        assert!(lines[1].starts_with("       16      16.0µs  █"));
+        assert!(lines[1].ends_with(" █"));
+        assert_eq!(lines[8].matches('█').count(), 40);
+    }
+}
@@ -2,16 +2,27 @@ use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
 use crate::template::Day;
+use crate::template::counters::HardwareCounters;
+use crate::template::memory::MemoryUsage;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Heap usage, if measured with `cargo time --memory`.
+    pub part_1_memory: Option<MemoryUsage>,
+    pub part_2_memory: Option<MemoryUsage>,
+    /// Hardware events per iteration, if counted with `cargo time --counters`.
+    pub part_1_counters: Option<HardwareCounters>,
+    pub part_2_counters: Option<HardwareCounters>,
+    /// The machine the timings were taken on, see `machine::cpu_model` and `machine::rustc_version`.
+    pub cpu_model: Option<String>,
+    pub rustc_version: Option<String>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +62,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -133,6 +144,33 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, memory) in [
+            ("part_1_memory", value.part_1_memory),
+            ("part_2_memory", value.part_2_memory),
+        ] {
+            if let Some(memory) = memory {
+                map.insert(key.into(), JsonValue::from(&memory));
+            }
+        }
+
+        for (key, counters) in [
+            ("part_1_counters", value.part_1_counters),
+            ("part_2_counters", value.part_2_counters),
+        ] {
+            if let Some(counters) = counters {
+                map.insert(key.into(), JsonValue::from(&counters));
+            }
+        }
+
+        for (key, value) in [
+            ("cpu_model", &value.cpu_model),
+            ("rustc_version", &value.rustc_version),
+        ] {
+            if let Some(value) = value {
+                map.insert(key.into(), JsonValue::String(value.clone()));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +204,143 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // timings stored without `--memory` have no memory keys
+        let memory = |key: &str| {
+            json.get(key)
+                .map(|v| {
+                    MemoryUsage::try_from(v).map_err(|e| format!("Expected timing.{key}: {e}"))
+                })
+                .transpose()
+        };
+        // timings stored before the machine was recorded have no machine keys
+        let text = |key: &str| {
+            json.get(key)
+                .map(|v| {
+                    v.get::<String>()
+                        .cloned()
+                        .ok_or(format!("Expected timing.{key} to be a string."))
+                })
+                .transpose()
+        };
+        let counters = |key: &str| {
+            json.get(key)
+                .map(|v| {
+                    HardwareCounters::try_from(v).map_err(|e| format!("Expected timing.{key}: {e}"))
+                })
+                .transpose()
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_memory: memory("part_1_memory")?,
+            part_2_memory: memory("part_2_memory")?,
+            part_1_counters: counters("part_1_counters")?,
+            part_2_counters: counters("part_2_counters")?,
+            cpu_model: text("cpu_model")?,
+            rustc_version: text("rustc_version")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryUsage> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryUsage) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryUsage {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected an object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as usize)
+                .ok_or(format!("expected {key} to be a number."))
+        };
+
+        Ok(MemoryUsage {
+            peak_bytes: number("peak_bytes")?,
+            total_bytes: number("total_bytes")?,
+            allocations: number("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HardwareCounters> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HardwareCounters) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "instructions".into(),
+            JsonValue::Number(value.instructions as f64),
+        );
+        map.insert("cycles".into(), JsonValue::Number(value.cycles as f64));
+        map.insert("ipc".into(), JsonValue::Number(value.ipc()));
+        map.insert(
+            "cache_misses".into(),
+            JsonValue::Number(value.cache_misses as f64),
+        );
+        map.insert(
+            "branch_misses".into(),
+            JsonValue::Number(value.branch_misses as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HardwareCounters {
+    type Error = String;
+
+    /// Reads the counters per iteration. The stored IPC follows from them and is not read.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected an object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("expected {key} to be a number."))
+        };
+
+        Ok(HardwareCounters {
+            instructions: number("instructions")?,
+            cycles: number("cycles")?,
+            cache_misses: number("cache_misses")?,
+            branch_misses: number("branch_misses")?,
         })
     }
 }
@@ -191,25 +361,33 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    ..Default::default()
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use tinyjson::JsonValue;
+
+        use crate::{
+            day,
+            template::{counters::HardwareCounters, memory::MemoryUsage, timings::Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -221,6 +399,52 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_memory, None);
+        }
+
+        #[test]
+        fn handles_memory_usage() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000,
+                "part_1_memory": { "peak_bytes": 2048, "total_bytes": 4096, "allocations": 3 } }] }"#
+                .to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1_memory,
+                Some(MemoryUsage {
+                    peak_bytes: 2048,
+                    total_bytes: 4096,
+                    allocations: 3
+                })
+            );
+            assert_eq!(timing.part_2_memory, None);
+
+            let round_trip =
+                Timings::try_from(JsonValue::from(timings.clone()).stringify().unwrap()).unwrap();
+            assert_eq!(round_trip.data[0].part_1_memory, timing.part_1_memory);
+        }
+
+        #[test]
+        fn handles_hardware_counters() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000,
+                "part_2_counters": { "instructions": 9000, "cycles": 4000, "ipc": 2.25, "cache_misses": 12, "branch_misses": 34 } }] }"#
+                .to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_counters, None);
+            assert_eq!(
+                timing.part_2_counters,
+                Some(HardwareCounters {
+                    instructions: 9000,
+                    cycles: 4000,
+                    cache_misses: 12,
+                    branch_misses: 34
+                })
+            );
+
+            let round_trip =
+                Timings::try_from(JsonValue::from(timings.clone()).stringify().unwrap()).unwrap();
+            assert_eq!(round_trip.data[0].part_2_counters, timing.part_2_counters);
         }
 
         #[test]
@@ -282,10 +506,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +521,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,10 +536,11 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -334,6 +561,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +582,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -0,0 +1,256 @@
+/// Records intermediate states of a solution as JSON lines, to be stepped through with `cargo replay`.
+///
+/// Solutions call [`frame`] behind an [`enabled`] check. Recording is enabled by running the binary
+/// with `--trace` (to `data/traces/NN.jsonl`) or `--trace=<path>`, and paused during benchmarks.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::io::{LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{env, process};
+
+use tinyjson::JsonValue;
+
+use crate::grid::Grid;
+use crate::template::Day;
+use crate::viz::Canvas;
+
+/// Where `--trace` writes frames if no path is given.
+pub fn default_path(day: Day) -> PathBuf {
+    Path::new("data")
+        .join("traces")
+        .join(format!("{day}.jsonl"))
+}
+
+/// A state that can be recorded as a frame.
+pub trait Snapshot {
+    /// Renders the state as text. It may contain ANSI colour codes.
+    fn render(&self) -> String;
+}
+
+impl Snapshot for str {
+    fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Snapshot for String {
+    fn render(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T: Display> Snapshot for Grid<T> {
+    fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Snapshot for Canvas {
+    fn render(&self) -> String {
+        self.to_ansi()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One recorded state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub part: u8,
+    /// The 1-based number of the frame within its part.
+    pub index: usize,
+    pub state: String,
+}
+
+impl From<&Frame> for JsonValue {
+    fn from(value: &Frame) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("index".into(), JsonValue::Number(value.index as f64));
+        map.insert("state".into(), JsonValue::String(value.state.clone()));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Frame {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected frame to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected frame.{key} to be a number."))
+        };
+
+        let state = json
+            .get("state")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected frame.state to be a string.")?;
+
+        Ok(Frame {
+            part: number("part")? as u8,
+            index: number("index")? as usize,
+            state: state.clone(),
+        })
+    }
+}
+
+/// Reads all frames of a trace file.
+pub fn read_file(path: &Path) -> Result<Vec<Frame>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            JsonValue::from_str(line)
+                .map_err(|_| "not valid JSON".to_string())
+                .and_then(|json| Frame::try_from(&json))
+                .map_err(|e| format!("{}:{}: {e}", path.display(), idx + 1))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+struct Recorder {
+    file: Option<LineWriter<fs::File>>,
+    part: u8,
+    index: usize,
+}
+
+static RECORDER: Mutex<Recorder> = Mutex::new(Recorder {
+    file: None,
+    part: 0,
+    index: 0,
+});
+
+/// Whether frames are recorded. Solutions should check this before building a snapshot.
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Starts recording if `--trace` or `--trace=<path>` was passed, replacing any earlier trace.
+/// Called by the `solution!` macro.
+pub fn init_from_args(day: Day) {
+    let Some(path) = env::args().find_map(|arg| match arg.as_str() {
+        "--trace" => Some(default_path(day)),
+        _ => arg.strip_prefix("--trace=").map(PathBuf::from),
+    }) else {
+        return;
+    };
+
+    let file = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::File::create(&path));
+
+    match file {
+        Ok(file) => {
+            RECORDER.lock().unwrap().file = Some(LineWriter::new(file));
+            ENABLED.store(true, Ordering::Relaxed);
+        }
+        Err(e) => {
+            eprintln!("Failed to create trace file {}: {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+/// Starts numbering frames for a part.
+pub fn begin_part(part: u8) {
+    let mut recorder = RECORDER.lock().unwrap();
+    recorder.part = part;
+    recorder.index = 0;
+}
+
+/// Records a frame, if tracing is enabled.
+pub fn frame<S: Snapshot + ?Sized>(state: &S) {
+    if !enabled() {
+        return;
+    }
+
+    let mut recorder = RECORDER.lock().unwrap();
+    recorder.index += 1;
+    let frame = Frame {
+        part: recorder.part,
+        index: recorder.index,
+        state: state.render(),
+    };
+
+    let line = JsonValue::from(&frame)
+        .stringify()
+        .map_err(|e| e.to_string());
+    let Some(file) = recorder.file.as_mut() else {
+        return;
+    };
+    if let Err(e) = line.and_then(|line| writeln!(file, "{line}").map_err(|e| e.to_string())) {
+        eprintln!("Failed to write trace frame: {e}");
+        process::exit(1);
+    }
+}
+
+/// Stops recording until the returned guard is dropped.
+#[must_use]
+pub fn pause() -> Paused {
+    Paused {
+        was_enabled: ENABLED.swap(false, Ordering::Relaxed),
+    }
+}
+
+/// Resumes recording when dropped, see [`pause`].
+pub struct Paused {
+    was_enabled: bool,
+}
+
+impl Drop for Paused {
+    fn drop(&mut self) {
+        ENABLED.store(self.was_enabled, Ordering::Relaxed);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{Frame, Snapshot};
+    use crate::grid::Grid;
+
+    #[test]
+    fn renders_snapshots() {
+        let grid = Grid::parse("#.\n.#\n", |c| c);
+        assert_eq!(grid.render(), "#.\n.#");
+        assert_eq!("state".render(), "state");
+    }
+
+    #[test]
+    fn round_trips_frames() {
+        let frame = Frame {
+            part: 2,
+            index: 7,
+            state: "\x1b[31m@\x1b[0m.\n..".into(),
+        };
+        let line = JsonValue::from(&frame).stringify().unwrap();
+        assert!(!line.contains('\n'));
+
+        let json = JsonValue::from_str(&line).unwrap();
+        assert_eq!(Frame::try_from(&json), Ok(frame));
+    }
+}
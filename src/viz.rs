@@ -0,0 +1,614 @@
+/// Renders grids, point sets and segments to the terminal or to numbered SVG/PNG frames.
+///
+/// Solutions call [`frame`] behind an [`enabled`] check, so the hooks cost a single atomic load
+/// unless the binary runs with `--visualize`. Hooks are paused while a part is benchmarked.
+use std::fmt::{Display, Write as _};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use std::{env, fs, process, thread};
+
+use crate::geometry::{Point2, Scalar, Segment};
+use crate::grid::{Grid, Pos};
+use crate::template::{ANSI_RESET, Day};
+
+/// How long a terminal frame is shown before the next one replaces it.
+const FRAME_DELAY: Duration = Duration::from_millis(50);
+
+/// Edge length of a cell in SVG and PNG frames, in pixels.
+const CELL_SIZE: usize = 4;
+
+/// Background of SVG and PNG frames.
+const BACKGROUND: (u8, u8, u8) = (15, 15, 35);
+
+/// Where frames are sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// Redraws each frame on stderr, with ANSI colours.
+    Terminal,
+    /// Writes each frame to `data/viz/NN/partP-NNNN.svg`.
+    Svg,
+    /// Writes each frame to `data/viz/NN/partP-NNNN.png`. Requires the `png-frames` feature.
+    Png,
+}
+
+impl Output {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Terminal => "terminal",
+            Self::Svg => "svg",
+            Self::Png => "png",
+        }
+    }
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "terminal" => Ok(Self::Terminal),
+            "svg" => Ok(Self::Svg),
+            "png" => Ok(Self::Png),
+            _ => Err(format!(
+                "unknown frame format {s:?}, expected terminal, svg or png"
+            )),
+        }
+    }
+}
+
+impl Display for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+struct State {
+    output: Output,
+    day: Option<Day>,
+    part: u8,
+    frame: usize,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    output: Output::Terminal,
+    day: None,
+    part: 0,
+    frame: 0,
+});
+
+/// Whether frames are shown. Solutions should check this before building a [`Canvas`].
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Enables the hooks if `--visualize` was passed, sending frames to the format given by `--frames`.
+/// Called by the `solution!` macro.
+pub fn init_from_args(day: Day) {
+    let args: Vec<String> = env::args().collect();
+    if !args.iter().any(|x| x == "--visualize") {
+        return;
+    }
+
+    let output = match args.iter().position(|x| x == "--frames") {
+        Some(idx) => match args.get(idx + 1).map(|x| x.parse::<Output>()) {
+            Some(Ok(output)) => output,
+            Some(Err(e)) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!("Unexpected command-line input. Format: --frames <terminal|svg|png>");
+                process::exit(1);
+            }
+        },
+        None => Output::Terminal,
+    };
+
+    if output == Output::Png && !cfg!(feature = "png-frames") {
+        eprintln!("PNG frames require the `png-frames` feature.");
+        process::exit(1);
+    }
+
+    let mut state = STATE.lock().unwrap();
+    state.output = output;
+    state.day = Some(day);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Starts numbering frames for a part, removing frames left over from earlier runs.
+pub fn begin_part(part: u8) {
+    let mut state = STATE.lock().unwrap();
+    state.part = part;
+    state.frame = 0;
+
+    if !enabled() || state.output == Output::Terminal {
+        return;
+    }
+
+    let dir = frame_dir(&state);
+    if let Ok(entries) = fs::read_dir(&dir) {
+        let prefix = format!("part{part}-");
+        for entry in entries.filter_map(Result::ok) {
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("Failed to create {}: {e}", dir.display());
+        process::exit(1);
+    }
+}
+
+/// Shows a frame, if the hooks are enabled.
+pub fn frame(canvas: &Canvas) {
+    if !enabled() {
+        return;
+    }
+
+    let mut state = STATE.lock().unwrap();
+    state.frame += 1;
+
+    let result = match state.output {
+        Output::Terminal => {
+            eprint!(
+                "\x1b[2J\x1b[HPart {} · frame {}\n{}",
+                state.part,
+                state.frame,
+                canvas.to_ansi()
+            );
+            thread::sleep(FRAME_DELAY);
+            Ok(())
+        }
+        Output::Svg => fs::write(frame_path(&state), canvas.to_svg(CELL_SIZE)),
+        #[cfg(feature = "png-frames")]
+        Output::Png => canvas.write_png(&frame_path(&state), CELL_SIZE),
+        #[cfg(not(feature = "png-frames"))]
+        Output::Png => unreachable!("checked by init_from_args"),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Failed to write frame: {e}");
+        process::exit(1);
+    }
+}
+
+/// Disables the hooks until the returned guard is dropped.
+#[must_use]
+pub fn pause() -> Paused {
+    Paused {
+        was_enabled: ENABLED.swap(false, Ordering::Relaxed),
+    }
+}
+
+/// Restores the hooks when dropped, see [`pause`].
+pub struct Paused {
+    was_enabled: bool,
+}
+
+impl Drop for Paused {
+    fn drop(&mut self) {
+        ENABLED.store(self.was_enabled, Ordering::Relaxed);
+    }
+}
+
+fn frame_dir(state: &State) -> PathBuf {
+    let day = state.day.map(|day| day.to_string()).unwrap_or_default();
+    PathBuf::from("data").join("viz").join(day)
+}
+
+fn frame_path(state: &State) -> PathBuf {
+    frame_dir(state).join(format!(
+        "part{}-{:04}.{}",
+        state.part,
+        state.frame,
+        state.output.as_str()
+    ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    /// The escape sequence that sets this foreground colour.
+    pub fn ansi(&self) -> &'static str {
+        match self {
+            Self::Red => "\x1b[31m",
+            Self::Green => "\x1b[32m",
+            Self::Yellow => "\x1b[33m",
+            Self::Blue => "\x1b[34m",
+            Self::Magenta => "\x1b[35m",
+            Self::Cyan => "\x1b[36m",
+            Self::White => "\x1b[37m",
+            Self::Gray => "\x1b[90m",
+        }
+    }
+
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Self::Red => (230, 70, 70),
+            Self::Green => (80, 200, 100),
+            Self::Yellow => (240, 200, 60),
+            Self::Blue => (80, 130, 240),
+            Self::Magenta => (200, 90, 220),
+            Self::Cyan => (70, 200, 220),
+            Self::White => (235, 235, 235),
+            Self::Gray => (110, 110, 120),
+        }
+    }
+}
+
+/// A symbol with an optional colour. Cells with a space as symbol are drawn as background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Option<Color>,
+}
+
+impl Cell {
+    pub const EMPTY: Self = Self::new(' ');
+
+    pub const fn new(symbol: char) -> Self {
+        Self {
+            symbol,
+            color: None,
+        }
+    }
+
+    pub const fn colored(symbol: char, color: Color) -> Self {
+        Self {
+            symbol,
+            color: Some(color),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.symbol == ' '
+    }
+
+    fn rgb(&self) -> (u8, u8, u8) {
+        self.color.unwrap_or(Color::White).rgb()
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// Maps world coordinates onto canvas cells: `cell = (value - min) / scale`.
+#[derive(Clone, Copy, Debug)]
+struct Projection {
+    min: (i128, i128),
+    scale: i128,
+}
+
+/// A grid of coloured cells to be shown as a frame.
+///
+/// ```
+/// # use advent_of_code::geometry::Point2;
+/// # use advent_of_code::viz::{Canvas, Cell};
+/// let points = [Point2::new(10, 10), Point2::new(13, 11)];
+/// let mut canvas = Canvas::fitting(points, 80);
+/// for point in points {
+///     canvas.plot(point, Cell::new('#'));
+/// }
+/// assert_eq!(canvas.to_string(), "#   \n   #");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    cells: Grid<Cell>,
+    projection: Projection,
+}
+
+impl Canvas {
+    /// An empty canvas whose cells are addressed by grid positions.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            cells: Grid::filled(width, height, Cell::EMPTY),
+            projection: Projection {
+                min: (0, 0),
+                scale: 1,
+            },
+        }
+    }
+
+    /// Draws every cell of a grid.
+    pub fn from_grid<T>(grid: &Grid<T>, f: impl Fn(&T) -> Cell) -> Self {
+        let mut canvas = Self::new(grid.width(), grid.height());
+        for pos in grid.positions() {
+            canvas.cells[pos] = f(&grid[pos]);
+        }
+        canvas
+    }
+
+    /// An empty canvas covering the bounding box of some points, scaled down so that neither side
+    /// exceeds `max_size` cells. Several world coordinates may then share a cell.
+    ///
+    /// # Panics
+    /// If there are no points or `max_size` is zero.
+    pub fn fitting<T: Scalar>(
+        points: impl IntoIterator<Item = Point2<T>>,
+        max_size: usize,
+    ) -> Self {
+        assert!(max_size > 0, "canvas must have a size");
+        let (min, max) = points
+            .into_iter()
+            .map(|p| (p.x.as_i128(), p.y.as_i128()))
+            .fold(None, |bounds, (x, y)| match bounds {
+                None => Some(((x, y), (x, y))),
+                Some(((min_x, min_y), (max_x, max_y))) => {
+                    Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))))
+                }
+            })
+            .expect("canvas must cover at least one point");
+
+        let span = (max.0 - min.0).max(max.1 - min.1) + 1;
+        let scale = (span + max_size as i128 - 1) / max_size as i128;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let size = |from: i128, to: i128| ((to - from) / scale + 1) as usize;
+
+        Self {
+            cells: Grid::filled(size(min.0, max.0), size(min.1, max.1), Cell::EMPTY),
+            projection: Projection { min, scale },
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&Cell> {
+        self.cells.get(pos)
+    }
+
+    /// Sets a cell by its position on the canvas, ignoring positions outside of it.
+    pub fn set(&mut self, pos: Pos, cell: Cell) {
+        if let Some(target) = self.cells.get_mut(pos) {
+            *target = cell;
+        }
+    }
+
+    /// The cell a point in world coordinates falls into, if it is on the canvas.
+    fn project<T: Scalar>(&self, point: Point2<T>) -> Option<Pos> {
+        let Projection { min, scale } = self.projection;
+        let (x, y) = (point.x.as_i128() - min.0, point.y.as_i128() - min.1);
+        if x < 0 || y < 0 {
+            return None;
+        }
+        let pos = (
+            usize::try_from(x / scale).ok()?,
+            usize::try_from(y / scale).ok()?,
+        );
+        (pos.0 < self.width() && pos.1 < self.height()).then_some(pos)
+    }
+
+    /// Draws a point given in world coordinates.
+    pub fn plot<T: Scalar>(&mut self, point: Point2<T>, cell: Cell) {
+        if let Some(pos) = self.project(point) {
+            self.cells[pos] = cell;
+        }
+    }
+
+    /// Draws a segment given in world coordinates.
+    pub fn draw_segment<T: Scalar>(&mut self, segment: &Segment<T>, cell: Cell) {
+        self.fill_rect(segment.start, segment.end, cell);
+    }
+
+    /// Fills the rectangle spanned by two opposite corners given in world coordinates.
+    pub fn fill_rect<T: Scalar>(&mut self, a: Point2<T>, b: Point2<T>, cell: Cell) {
+        // there is no cell to clamp the corners to
+        if self.width() == 0 || self.height() == 0 {
+            return;
+        }
+        let clamp = |p: Point2<T>| {
+            let Projection { min, scale } = self.projection;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let axis = |value: T, min: i128, len: usize| {
+                ((value.as_i128() - min).max(0) / scale).min(len as i128 - 1) as usize
+            };
+            (
+                axis(p.x, min.0, self.width()),
+                axis(p.y, min.1, self.height()),
+            )
+        };
+        let ((ax, ay), (bx, by)) = (clamp(a), clamp(b));
+
+        for y in ay.min(by)..=ay.max(by) {
+            for x in ax.min(bx)..=ax.max(bx) {
+                self.cells[(x, y)] = cell;
+            }
+        }
+    }
+
+    /// Renders the canvas with ANSI colour codes, one line per row.
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.rows() {
+            let mut current = None;
+            for cell in row {
+                if cell.color != current && !cell.is_empty() {
+                    out.push_str(cell.color.map_or(ANSI_RESET, |color| color.ansi()));
+                    current = cell.color;
+                }
+                out.push(cell.symbol);
+            }
+            if current.is_some() {
+                out.push_str(ANSI_RESET);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Renders the canvas as an SVG image with a square of `cell_size` pixels per non-empty cell.
+    pub fn to_svg(&self, cell_size: usize) -> String {
+        let rgb = |(r, g, b): (u8, u8, u8)| format!("rgb({r},{g},{b})");
+        let (width, height) = (self.width() * cell_size, self.height() * cell_size);
+
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            rgb(BACKGROUND)
+        );
+        for pos in self.cells.positions() {
+            let cell = &self.cells[pos];
+            if cell.is_empty() {
+                continue;
+            }
+            let _ = writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{cell_size}\" height=\"{cell_size}\" fill=\"{}\"/>",
+                pos.0 * cell_size,
+                pos.1 * cell_size,
+                rgb(cell.rgb())
+            );
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Writes the canvas as a PNG image with a square of `cell_size` pixels per cell.
+    #[cfg(feature = "png-frames")]
+    pub fn write_png(&self, path: &std::path::Path, cell_size: usize) -> std::io::Result<()> {
+        let (width, height) = (self.width() * cell_size, self.height() * cell_size);
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                let cell = &self.cells[(x / cell_size, y / cell_size)];
+                let (r, g, b) = if cell.is_empty() {
+                    BACKGROUND
+                } else {
+                    cell.rgb()
+                };
+                pixels.extend([r, g, b]);
+            }
+        }
+
+        let file = std::io::BufWriter::new(fs::File::create(path)?);
+        #[allow(clippy::cast_possible_truncation)]
+        let mut encoder = png::Encoder::new(file, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(std::io::Error::other)?;
+        writer
+            .write_image_data(&pixels)
+            .map_err(std::io::Error::other)
+    }
+}
+
+/// The symbols without colours, one line per row.
+impl Display for Canvas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let grid = self.cells.map(|cell| cell.symbol);
+        write!(f, "{grid}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Canvas, Cell, Color, Output};
+    use crate::geometry::{Point2, Segment};
+    use crate::grid::Grid;
+
+    #[test]
+    fn renders_grids() {
+        let grid = Grid::parse("@.\n.@\n", |c| c == '@');
+        let canvas = Canvas::from_grid(&grid, |roll| {
+            if *roll {
+                Cell::colored('@', Color::Red)
+            } else {
+                Cell::new('.')
+            }
+        });
+        assert_eq!(canvas.to_string(), "@.\n.@");
+        assert_eq!(canvas.to_ansi(), "\x1b[31m@\x1b[0m.\n.\x1b[31m@\x1b[0m\n");
+    }
+
+    #[test]
+    fn scales_large_coordinates() {
+        let corners = [Point2::new(0u64, 0), Point2::new(999, 499)];
+        let mut canvas = Canvas::fitting(corners, 10);
+        assert_eq!((canvas.width(), canvas.height()), (10, 5));
+
+        canvas.draw_segment(
+            &Segment::new(Point2::new(0u64, 250), Point2::new(999, 250)),
+            Cell::new('-'),
+        );
+        canvas.plot(Point2::new(999u64, 499), Cell::new('#'));
+        canvas.plot(Point2::new(2000u64, 0), Cell::new('x'));
+        assert_eq!(
+            canvas.to_string(),
+            "          \n          \n----------\n          \n         #"
+        );
+    }
+
+    #[test]
+    fn fills_rectangles() {
+        let mut canvas = Canvas::fitting([Point2::new(-2i32, -2), Point2::new(2, 2)], 5);
+        canvas.fill_rect(Point2::new(1, 1), Point2::new(-1, -1), Cell::new('#'));
+        assert_eq!(canvas.to_string(), "     \n ### \n ### \n ### \n     ");
+    }
+
+    #[test]
+    fn ignores_rectangles_on_empty_canvas() {
+        let mut canvas = Canvas::new(0, 3);
+        canvas.fill_rect(Point2::new(0u32, 0), Point2::new(5, 5), Cell::new('#'));
+        assert_eq!(canvas.width(), 0);
+
+        let mut canvas = Canvas::new(3, 0);
+        canvas.draw_segment(
+            &Segment::new(Point2::new(0u32, 0), Point2::new(2, 0)),
+            Cell::new('-'),
+        );
+        assert_eq!(canvas.height(), 0);
+    }
+
+    #[test]
+    fn renders_svg() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.set((1, 1), Cell::colored('#', Color::Green));
+        let svg = canvas.to_svg(4);
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"12\" height=\"8\">")
+        );
+        assert!(
+            svg.contains(
+                "<rect x=\"4\" y=\"4\" width=\"4\" height=\"4\" fill=\"rgb(80,200,100)\"/>"
+            )
+        );
+        assert_eq!(svg.matches("<rect").count(), 2);
+    }
+
+    #[test]
+    fn parses_outputs() {
+        assert_eq!("svg".parse(), Ok(Output::Svg));
+        assert!("gif".parse::<Output>().is_err());
+    }
+}